@@ -0,0 +1,36 @@
+//! Compares narrow draws from an [`Acorn`] generator against the same draws from a [`BufferedAcorn`].
+//!
+//! Run with `cargo bench --bench buffered`.
+use std::hint::black_box;
+use std::time::Instant;
+
+use acorn_prng::{Acorn, BufferedAcorn, Order, Seed};
+
+const ITERATIONS: u32 = 200_000;
+
+fn report(name: &str, start: Instant) {
+    println!("{name:<32} {:>8.1} ns/iter", start.elapsed().as_secs_f64() * 1e9 / f64::from(ITERATIONS));
+}
+
+fn main() {
+    let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    let mut buffered = BufferedAcorn::new(Acorn::new(Order::new(45), Seed::new(1_000_000)));
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {black_box(prng.generate_u8_between_range(0..=1));}
+    report("coin flip Acorn", start);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {black_box(buffered.generate_bool());}
+    report("coin flip BufferedAcorn", start);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {black_box(prng.generate_u8_between_range(1..=6));}
+    report("die roll Acorn", start);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {black_box(buffered.generate_u8_between_range(1..=6));}
+    report("die roll BufferedAcorn", start);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {black_box(prng.generate_usize_between_range(0..=999));}
+    report("index pick Acorn", start);
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {black_box(buffered.generate_usize_between_range(0..=999));}
+    report("index pick BufferedAcorn", start);
+}
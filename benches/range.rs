@@ -0,0 +1,34 @@
+//! Compares generating numbers within a range using masking and rejection ([`Version::V1`]) against
+//! Lemire's multiply-and-reject method ([`Version::V3`]).
+//!
+//! Run with `cargo bench --bench range`.
+use std::hint::black_box;
+use std::time::Instant;
+
+use acorn_prng::{AcornBuilder, Version};
+
+const ITERATIONS: u32 = 200_000;
+
+fn bench(name: &str, version: Version, upper_bound: u128) {
+    let mut prng = AcornBuilder::new().version(version).build();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        black_box(prng.generate_u128_between_range(0..=black_box(upper_bound)));
+    }
+    let elapsed = start.elapsed();
+    println!("{name:<24} {version:?}: {:>8.1} ns/iter", elapsed.as_secs_f64() * 1e9 / f64::from(ITERATIONS));
+}
+
+fn main() {
+    for (name, upper_bound) in [
+        ("0..=5", 5),
+        ("0..=2^16", 1 << 16),
+        ("0..=2^16 + 1", (1 << 16) + 1),
+        ("0..=2^64 + 1", (1 << 64) + 1),
+        ("0..=10^30", 10_u128.pow(30)),
+        ("0..=2^119 + 2^118", (1 << 119) + (1 << 118)),
+    ] {
+        bench(name, Version::V1, upper_bound);
+        bench(name, Version::V3, upper_bound);
+    }
+}
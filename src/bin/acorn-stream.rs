@@ -0,0 +1,161 @@
+//! # acorn-stream
+//!
+//! Writes the raw output of an [Acorn](../acorn_prng/struct.Acorn.html) generator to stdout as an endless binary
+//! stream for piping into statistical test suites such as `PractRand`, `dieharder` or `TestU01`.
+//!
+//! Only the meaningful bits of each raw output (as many as the modulus allows) are emitted. They are packed
+//! least significant bit first without gaps and written as little-endian 32 or 64 bit words.
+//!
+//! ```text
+//! acorn-stream --order 45 --seed 1000000 --bits 64 | RNG_test stdin64
+//! acorn-stream --bits 32 | dieharder -g 200 -a
+//! ```
+use std::io::{ErrorKind, Write};
+use std::process::ExitCode;
+
+use acorn_prng::{Acorn, Modulus, Order, Seed};
+
+const USAGE: &str = "Usage: acorn-stream [--order <ORDER>] [--seed <SEED>] [--modulus <POWER>] [--bits 32|64]
+
+Writes raw ACORN output to stdout as an endless binary stream.
+
+Options:
+  --order <ORDER>    order of the generator, clamped between 45 and 65535 [default: 45]
+  --seed <SEED>      seed of the generator, clamped to at least 1000000 [default: 1000000]
+  --modulus <POWER>  modulus of the generator as a power of two, clamped between 30 and 120 [default: 120]
+  --bits 32|64       width of the little-endian words written to stdout [default: 64]
+  -h, --help         print this message";
+
+#[derive(Debug, Eq, PartialEq)]
+struct Options {
+    order: usize,
+    seed: u128,
+    modulus: u32,
+    bits: u32,
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let mut options = Options {order: 45, seed: 1_000_000, modulus: 120, bits: 64};
+        while let Some(arg) = args.next() {
+            if arg == "-h" || arg == "--help" {return Ok(None);}
+            let value = args.next().ok_or_else(|| format!("missing value for {arg}"))?;
+            let invalid = |_| format!("invalid value for {arg}: {value}");
+            match arg.as_str() {
+                "--order" => options.order = value.parse().map_err(invalid)?,
+                "--seed" => options.seed = value.parse().map_err(invalid)?,
+                "--modulus" => options.modulus = value.parse().map_err(invalid)?,
+                "--bits" => options.bits = match value.as_str() {
+                    "32" => 32,
+                    "64" => 64,
+                    _ => return Err(format!("invalid value for {arg}: {value} (expected 32 or 64)")),
+                },
+                _ => return Err(format!("unknown argument: {arg}")),
+            }
+        }
+        Ok(Some(options))
+    }
+}
+
+/// Packs values of arbitrary bit width into fixed width words without leaving gaps.
+struct BitPacker {
+    buffer: u128,
+    length: u32,
+    word_bits: u32,
+}
+
+impl BitPacker {
+    const fn new(word_bits: u32) -> Self {
+        Self {buffer: 0, length: 0, word_bits}
+    }
+    /// Push the lowest `bits` bits of `value` and call `emit` for every completed word.
+    fn push(&mut self, mut value: u128, mut bits: u32, mut emit: impl FnMut(u64)) {
+        while bits > 0 {
+            let take = bits.min(64);
+            self.buffer |= (value & ((1 << take) - 1)) << self.length;
+            self.length += take;
+            value >>= take;
+            bits -= take;
+            while self.length >= self.word_bits {
+                #[allow(clippy::cast_possible_truncation)]
+                emit(self.buffer as u64 & (u64::MAX >> (64 - self.word_bits)));
+                self.buffer >>= self.word_bits;
+                self.length -= self.word_bits;
+            }
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        },
+        Err(error) => {
+            eprintln!("acorn-stream: {error}\n\n{USAGE}");
+            return ExitCode::from(2);
+        },
+    };
+    let modulus = Modulus::new(options.modulus);
+    let bits = modulus.bits();
+    let mut prng = Acorn::with_modulus(Order::new(options.order), Seed::new(options.seed), modulus);
+    let mut packer = BitPacker::new(options.bits);
+    let mut stdout = std::io::BufWriter::with_capacity(1 << 16, std::io::stdout().lock());
+    let mut result = Ok(());
+    while result.is_ok() {
+        packer.push(prng.generate_u128(), bits, |word| {
+            if result.is_err() {return;}
+            #[allow(clippy::cast_possible_truncation)]
+            let written = if options.bits == 32 {
+                stdout.write_all(&(word as u32).to_le_bytes())
+            } else {
+                stdout.write_all(&word.to_le_bytes())
+            };
+            result = written;
+        });
+    }
+    match result {
+        Err(error) if error.kind() != ErrorKind::BrokenPipe => {
+            eprintln!("acorn-stream: {error}");
+            ExitCode::FAILURE
+        },
+        _ => ExitCode::SUCCESS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn parse(args: &[&str]) -> Result<Option<Options>, String> {
+        Options::parse(args.iter().map(ToString::to_string))
+    }
+    #[test]
+    fn parse_defaults() {
+        assert_eq!(parse(&[]), Ok(Some(Options {order: 45, seed: 1_000_000, modulus: 120, bits: 64})));
+    }
+    #[test]
+    fn parse_options() {
+        assert_eq!(parse(&["--order", "77", "--seed", "123456789", "--modulus", "64", "--bits", "32"]),
+            Ok(Some(Options {order: 77, seed: 123_456_789, modulus: 64, bits: 32})));
+        assert_eq!(parse(&["--help"]), Ok(None));
+        assert!(parse(&["--bits", "16"]).is_err());
+        assert!(parse(&["--seed"]).is_err());
+        assert!(parse(&["--unknown", "1"]).is_err());
+    }
+    #[test]
+    fn pack_without_gaps() {
+        let mut words = Vec::new();
+        let mut packer = BitPacker::new(64);
+        let value = (1 << 120) - 1;
+        for _ in 0..8 {packer.push(value, 120, |word| words.push(word));}
+        assert_eq!(words, [u64::MAX; 15]); // 8 * 120 bits fill exactly 15 words
+        let mut packer = BitPacker::new(32);
+        words.clear();
+        packer.push(0xAAAA_BBBB_CCCC_DDDD_EEEE, 80, |word| words.push(word));
+        assert_eq!(words, [0xDDDD_EEEE, 0xBBBB_CCCC]);
+        packer.push(0x1, 16, |word| words.push(word));
+        assert_eq!(words, [0xDDDD_EEEE, 0xBBBB_CCCC, 0x0001_AAAA]);
+    }
+}
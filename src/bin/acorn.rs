@@ -0,0 +1,320 @@
+//! # acorn
+//!
+//! Generates reproducible pseudo-random values from the command line using an
+//! [Acorn](../acorn_prng/struct.Acorn.html) generator.
+//!
+//! The resolved (clamped) order, seed and digit length are always reported so that any run can be reproduced. In
+//! plain mode they are written to stderr, in JSON mode they are part of the output object.
+//!
+//! ```text
+//! acorn --seed 123456789 range -6 6
+//! acorn --count 5 digits 4
+//! printf 'a\nb\nc\n' | acorn --json shuffle
+//! ```
+use std::io::{BufRead, Write};
+use std::process::ExitCode;
+
+use acorn_prng::{Acorn, Order, Seed};
+
+const USAGE: &str = "Usage: acorn [OPTIONS] <COMMAND>
+
+Generates reproducible pseudo-random values.
+
+Commands:
+  range <LO> <HI>    integers between LO and HI (inclusive), which may be negative
+  digits <N>         integers with exactly N decimal digits, clamped between 1 and 39
+  bytes <N>          N bytes
+  shuffle            the lines of stdin in a random order
+  float              floating point numbers between 0 (inclusive) and 1 (exclusive)
+  choose [ITEM...]   one of the given items, or one line of stdin when no items are given
+
+Options:
+  --order <ORDER>    order of the generator, clamped between 45 and 65535 [default: 45]
+  --seed <SEED>      seed of the generator, clamped to at least 1000000 [default: current time]
+  --count <COUNT>    number of values to generate for range, digits, float and choose [default: 1]
+  --json             print a JSON object instead of one value per line
+  -h, --help         print this message";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Range(u128, u128),
+    SignedRange(i128, i128),
+    Digits(usize),
+    Bytes(usize),
+    Shuffle,
+    Float,
+    Choose(Vec<String>),
+}
+
+#[derive(Debug, PartialEq)]
+struct Options {
+    order: usize,
+    seed: Option<u128>,
+    count: usize,
+    json: bool,
+    command: Command,
+}
+
+fn parse_number<T: core::str::FromStr>(name: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or_else(|| format!("missing value for {name}"))?;
+    value.parse().map_err(|_| format!("invalid value for {name}: {value}"))
+}
+
+impl Options {
+    fn parse(mut args: impl Iterator<Item = String>) -> Result<Option<Self>, String> {
+        let (mut order, mut seed, mut count, mut json) = (45, None, 1, false);
+        let mut positional = Vec::new();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => return Ok(None),
+                "--order" => order = parse_number(&arg, args.next())?,
+                "--seed" => seed = Some(parse_number(&arg, args.next())?),
+                "--count" => count = parse_number(&arg, args.next())?,
+                "--json" => json = true,
+                _ if arg.starts_with("--") => return Err(format!("unknown option: {arg}")),
+                _ => positional.push(arg),
+            }
+        }
+        let mut positional = positional.into_iter();
+        let name = positional.next().ok_or("missing command")?;
+        let command = match name.as_str() {
+            "range" => {
+                let (lo, hi) = (positional.next(), positional.next());
+                if let (Ok(lo), Ok(hi)) = (parse_number::<u128>("<LO>", lo.clone()), parse_number("<HI>", hi.clone())) {
+                    if lo > hi {return Err(format!("<LO> ({lo}) must not be greater than <HI> ({hi})"));}
+                    Command::Range(lo, hi)
+                } else {
+                    let (lo, hi): (i128, i128) = (parse_number("<LO>", lo)?, parse_number("<HI>", hi)?);
+                    if lo > hi {return Err(format!("<LO> ({lo}) must not be greater than <HI> ({hi})"));}
+                    Command::SignedRange(lo, hi)
+                }
+            },
+            "digits" => Command::Digits(parse_number("<N>", positional.next())?),
+            "bytes" => Command::Bytes(parse_number("<N>", positional.next())?),
+            "shuffle" => Command::Shuffle,
+            "float" => Command::Float,
+            "choose" => Command::Choose(positional.by_ref().collect()),
+            _ => return Err(format!("unknown command: {name}")),
+        };
+        if let Some(extra) = positional.next() {return Err(format!("unexpected argument: {extra}"));}
+        Ok(Some(Options {order, seed, count, json, command}))
+    }
+}
+
+#[derive(Debug, PartialEq)]
+enum Value {
+    Integer(u128),
+    Signed(i128),
+    Float(f64),
+    Text(String),
+}
+
+impl Value {
+    fn write_json(&self, out: &mut impl Write) -> std::io::Result<()> {
+        match self {
+            Value::Integer(number) => write!(out, "{number}"),
+            Value::Signed(number) => write!(out, "{number}"),
+            Value::Float(number) => write!(out, "{number:?}"),
+            Value::Text(text) => {
+                write!(out, "\"")?;
+                for c in text.chars() {
+                    match c {
+                        '"' => write!(out, "\\\"")?,
+                        '\\' => write!(out, "\\\\")?,
+                        '\n' => write!(out, "\\n")?,
+                        '\r' => write!(out, "\\r")?,
+                        '\t' => write!(out, "\\t")?,
+                        c if c.is_control() => write!(out, "\\u{:04x}", u32::from(c))?,
+                        c => write!(out, "{c}")?,
+                    }
+                }
+                write!(out, "\"")
+            },
+        }
+    }
+}
+
+impl core::fmt::Display for Value {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Value::Integer(number) => write!(f, "{number}"),
+            Value::Signed(number) => write!(f, "{number}"),
+            Value::Float(number) => write!(f, "{number}"),
+            Value::Text(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+/// The settings of a run after clamping, which are reported so that it can be reproduced.
+struct Resolved {
+    order: Order,
+    seed: Seed,
+    digits: Option<usize>,
+}
+
+impl Resolved {
+    fn new(options: &Options, seed: Seed) -> Self {
+        let digits = match options.command {
+            Command::Digits(length) => Some(length.clamp(1, 39)),
+            _ => None,
+        };
+        Resolved {order: Order::new(options.order), seed, digits}
+    }
+    fn write_plain(&self, out: &mut impl Write) -> std::io::Result<()> {
+        write!(out, "acorn: --order {} --seed {}", self.order.get(), self.seed.get())?;
+        if let Some(length) = self.digits {write!(out, " digits {length}")?;}
+        writeln!(out)
+    }
+}
+
+fn read_lines(input: impl BufRead) -> std::io::Result<Vec<String>> {
+    input.lines().collect()
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn generate(prng: &mut Acorn, command: Command, count: usize, input: impl BufRead) -> std::io::Result<Vec<Value>> {
+    Ok(match command {
+        Command::Range(lo, hi) => (0..count).map(|_| Value::Integer(prng.generate_u128_between_range(lo..=hi))).collect(),
+        Command::SignedRange(lo, hi) => (0..count).map(|_| Value::Signed(prng.generate_range(lo..=hi))).collect(),
+        Command::Digits(length) => (0..count).map(|_| Value::Integer(prng.generate_fixed_length_u128(length))).collect(),
+        Command::Bytes(length) => {
+            (0..length).map(|_| Value::Integer(prng.generate_bits(8))).collect() // the highest bits of each output
+        },
+        Command::Shuffle => {
+            let mut lines = read_lines(input)?;
+            for index in (1..lines.len()).rev() {
+                lines.swap(index, prng.generate_usize_between_range(0..=index));
+            }
+            lines.into_iter().map(Value::Text).collect()
+        },
+        Command::Float => {
+            let scale = (1_u64 << 53) as f64;
+            (0..count).map(|_| Value::Float(prng.generate_bits(53) as f64 / scale)).collect()
+        },
+        Command::Choose(mut items) => {
+            if items.is_empty() {items = read_lines(input)?;}
+            if items.is_empty() {return Ok(Vec::new());}
+            (0..count).map(|_| Value::Text(items[prng.generate_usize_between_range(0..=items.len() - 1)].clone()))
+                .collect()
+        },
+    })
+}
+
+fn write_output(out: &mut impl Write, resolved: &Resolved, values: &[Value], json: bool) -> std::io::Result<()> {
+    if json {
+        write!(out, "{{\"order\":{},\"seed\":{},", resolved.order.get(), resolved.seed.get())?;
+        if let Some(length) = resolved.digits {write!(out, "\"digits\":{length},")?;}
+        write!(out, "\"values\":[")?;
+        for (index, value) in values.iter().enumerate() {
+            if index > 0 {write!(out, ",")?;}
+            value.write_json(out)?;
+        }
+        writeln!(out, "]}}")
+    } else {
+        for value in values {writeln!(out, "{value}")?;}
+        Ok(())
+    }
+}
+
+fn main() -> ExitCode {
+    let options = match Options::parse(std::env::args().skip(1)) {
+        Ok(Some(options)) => options,
+        Ok(None) => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        },
+        Err(error) => {
+            eprintln!("acorn: {error}\n\n{USAGE}");
+            return ExitCode::from(2);
+        },
+    };
+    let seed = options.seed.map_or_else(Seed::from_system_time, Seed::new);
+    let resolved = Resolved::new(&options, seed);
+    if !options.json {
+        let _ = resolved.write_plain(&mut std::io::stderr().lock());
+    }
+    let mut prng = Acorn::new(resolved.order, resolved.seed);
+    let result = generate(&mut prng, options.command, options.count, std::io::stdin().lock())
+        .and_then(|values| write_output(&mut std::io::stdout().lock(), &resolved, &values, options.json));
+    match result {
+        Err(error) if error.kind() != std::io::ErrorKind::BrokenPipe => {
+            eprintln!("acorn: {error}");
+            ExitCode::FAILURE
+        },
+        _ => ExitCode::SUCCESS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    fn parse(args: &[&str]) -> Result<Option<Options>, String> {
+        Options::parse(args.iter().map(ToString::to_string))
+    }
+    fn run(args: &[&str], input: &str) -> String {
+        let options = parse(args).unwrap().unwrap();
+        let resolved = Resolved::new(&options, Seed::new(options.seed.unwrap()));
+        let mut prng = Acorn::new(resolved.order, resolved.seed);
+        let values = generate(&mut prng, options.command, options.count, input.as_bytes()).unwrap();
+        let mut out = Vec::new();
+        write_output(&mut out, &resolved, &values, options.json).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+    #[test]
+    fn parse_commands() {
+        assert_eq!(parse(&["--seed", "5", "--count", "3", "range", "1", "6"]),
+            Ok(Some(Options {order: 45, seed: Some(5), count: 3, json: false, command: Command::Range(1, 6)})));
+        assert_eq!(parse(&["choose", "a", "b", "--json"]).unwrap().unwrap().command,
+            Command::Choose(vec![String::from("a"), String::from("b")]));
+        assert_eq!(parse(&["--help"]), Ok(None));
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["range", "6", "1"]).is_err());
+        assert_eq!(parse(&["range", "-6", "6"]).unwrap().unwrap().command, Command::SignedRange(-6, 6));
+        assert!(parse(&["range", "6", "-6"]).is_err());
+        assert!(parse(&["range", "one", "6"]).is_err());
+        assert!(parse(&["digits"]).is_err());
+        assert!(parse(&["float", "extra"]).is_err());
+        assert!(parse(&["--verbose", "float"]).is_err());
+    }
+    #[test]
+    fn reproducible_output() {
+        assert_eq!(run(&["--seed", "1000000", "range", "71", "777"], ""), "571\n");
+        assert_eq!(run(&["--seed", "1000000", "digits", "3"], ""), "822\n");
+        assert_eq!(run(&["--seed", "1", "--order", "1", "--json", "digits", "3"], ""),
+            "{\"order\":45,\"seed\":1000000,\"digits\":3,\"values\":[822]}\n");
+        assert!(run(&["--seed", "1000000", "--json", "digits", "50"], "").contains("\"digits\":39,"));
+        let options = parse(&["--seed", "1000000", "digits", "0"]).unwrap().unwrap();
+        let mut out = Vec::new();
+        Resolved::new(&options, Seed::new(1_000_000)).write_plain(&mut out).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "acorn: --order 45 --seed 1000000 digits 1\n");
+    }
+    #[test]
+    fn negative_ranges() {
+        let numbers = run(&["--seed", "1000000", "--count", "200", "range", "-3", "3"], "");
+        let numbers: Vec<i128> = numbers.lines().map(|line| line.parse().unwrap()).collect();
+        assert!(numbers.iter().all(|number| (-3..=3).contains(number)));
+        assert!(numbers.contains(&-3) && numbers.contains(&3));
+        assert_eq!(run(&["--seed", "1000000", "--json", "range", "-170141183460469231731687303715884105728", "-1"], "")
+            .matches('-').count(), 1);
+    }
+    #[test]
+    fn shuffle_and_choose_lines() {
+        let shuffled = run(&["--seed", "1000000", "shuffle"], "a\nb\nc\nd\n");
+        let mut lines: Vec<&str> = shuffled.lines().collect();
+        lines.sort_unstable();
+        assert_eq!(lines, ["a", "b", "c", "d"]);
+        let chosen = run(&["--seed", "1000000", "--count", "10", "choose"], "x\ny\n");
+        assert!(chosen.lines().all(|line| line == "x" || line == "y"));
+        assert_eq!(run(&["--seed", "1000000", "--json", "choose", "a\"b"], ""),
+            "{\"order\":45,\"seed\":1000000,\"values\":[\"a\\\"b\"]}\n");
+    }
+    #[test]
+    fn floats_and_bytes_in_range() {
+        let floats = run(&["--seed", "1000000", "--count", "100", "float"], "");
+        assert!(floats.lines().map(|line| line.parse::<f64>().unwrap()).all(|number| (0.0..1.0).contains(&number)));
+        let bytes = run(&["--seed", "1000000", "bytes", "1024"], "");
+        let low: Vec<bool> = bytes.lines().map(|line| line.parse::<u8>().unwrap() % 2 == 1).collect();
+        assert_eq!(low.len(), 1024);
+        assert!((1..=256).all(|period| low.iter().zip(&low[period..]).any(|(a, b)| a != b))); // not the lowest bits
+    }
+}
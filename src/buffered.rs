@@ -0,0 +1,198 @@
+use core::ops::RangeInclusive;
+
+use crate::Acorn;
+
+/// An [Acorn](struct.Acorn.html) generator with a bit reservoir for cheap narrow draws.
+///
+/// Every call to [`Acorn::generate_u128`] costs a full pass over the [Order](struct.Order.html) but a coin flip
+/// only needs a single bit of the result. This wrapper keeps the unused high bits of each output and serves narrow
+/// requests from them.
+///
+/// The consumption of bits is deterministic:
+/// - The reservoir is refilled with the highest half of exactly one [`Acorn::generate_u128`] output, providing
+///   half as many bits as the [`Modulus::bits`] of the generator rounded down, only once every bit of the previous
+///   half has been used. The lower half is discarded because its bits have short periods (bit `n` repeats every
+///   2^(`n`+1) outputs), which would make some coin flips and small draws strictly periodic.
+/// - Bits are served from the highest remaining bit downwards. A request for more bits than remain takes all
+///   remaining bits as the highest bits of the result and the rest from the next output.
+/// - A number within a range with `n` possible values is drawn by taking the smallest number of bits that can
+///   represent `n - 1` and rejecting results above it.
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::{Acorn, BufferedAcorn, Order, Seed};
+///
+/// let mut prng = BufferedAcorn::new(Acorn::new(Order::new(45), Seed::new(1_000_000)));
+/// let coin = prng.generate_bool();
+/// let die = prng.generate_u8_between_range(1..=6);
+///
+/// assert!((1..=6).contains(&die));
+/// ```
+///
+/// [`Acorn::generate_u128`]: struct.Acorn.html#method.generate_u128
+/// [`Modulus::bits`]: struct.Modulus.html#method.bits
+#[derive(Debug, Eq, PartialEq)]
+pub struct BufferedAcorn {
+    prng: Acorn,
+    reservoir: u128,
+    available: u32,
+}
+
+impl BufferedAcorn {
+    /// Wrap an [Acorn](struct.Acorn.html) generator with an initially empty bit reservoir.
+    #[must_use]
+    pub const fn new(prng: Acorn) -> Self {
+        Self {prng, reservoir: 0, available: 0}
+    }
+    /// Unwrap the [Acorn](struct.Acorn.html) generator, discarding any bits left in the reservoir.
+    #[must_use]
+    pub const fn into_inner(self) -> Acorn {
+        self.prng
+    }
+    /// The number of bits left in the reservoir before the next output is generated.
+    #[must_use]
+    pub const fn available_bits(&self) -> u32 {
+        self.available
+    }
+    /// Generate a random number of up to 128 `bits` from the reservoir.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, BufferedAcorn, Order, Seed};
+    ///
+    /// let mut prng = BufferedAcorn::new(Acorn::new(Order::new(45), Seed::new(1_000_000)));
+    /// let number = prng.generate_bits(8);
+    ///
+    /// assert_eq!(79, number); // the highest 8 bits of the first output
+    /// assert_eq!(52, prng.available_bits()); // the rest of its highest 60 bits
+    /// ```
+    /// Note that `bits` is clamped to at most 128.
+    pub const fn generate_bits(&mut self, bits: u32) -> u128 {
+        let bits = if bits > 128 {128} else {bits};
+        let mut number = 0;
+        let mut filled = 0;
+        while filled < bits {
+            if self.available == 0 {
+                let width = self.prng.m.trailing_zeros();
+                self.available = width / 2;
+                self.reservoir = self.prng.generate_u128() >> (width - self.available);
+            }
+            let take = if bits - filled < self.available {bits - filled} else {self.available};
+            self.available -= take;
+            let taken = (self.reservoir >> self.available) & (u128::MAX >> (128 - take));
+            number = (number << take) | taken;
+            filled += take;
+        }
+        number
+    }
+    /// Generate a random [`bool`] from a single bit of the reservoir.
+    ///
+    /// [`bool`]: https://doc.rust-lang.org/core/primitive.bool.html
+    pub const fn generate_bool(&mut self) -> bool {
+        self.generate_bits(1) == 1
+    }
+    /// Generate a random [`u8`] within a given [`RangeInclusive`].
+    ///
+    /// [`u8`]: https://doc.rust-lang.org/core/primitive.u8.html
+    /// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn generate_u8_between_range(&mut self, range: RangeInclusive<u8>) -> u8 {
+        self.generate_number_between_range(*range.start() as u128, *range.end() as u128) as u8
+    }
+    /// Generate a random [`u16`] within a given [`RangeInclusive`].
+    ///
+    /// [`u16`]: https://doc.rust-lang.org/core/primitive.u16.html
+    /// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn generate_u16_between_range(&mut self, range: RangeInclusive<u16>) -> u16 {
+        self.generate_number_between_range(*range.start() as u128, *range.end() as u128) as u16
+    }
+    /// Generate a random [`u32`] within a given [`RangeInclusive`].
+    ///
+    /// [`u32`]: https://doc.rust-lang.org/core/primitive.u32.html
+    /// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn generate_u32_between_range(&mut self, range: RangeInclusive<u32>) -> u32 {
+        self.generate_number_between_range(*range.start() as u128, *range.end() as u128) as u32
+    }
+    /// Generate a random [`u64`] within a given [`RangeInclusive`].
+    ///
+    /// [`u64`]: https://doc.rust-lang.org/core/primitive.u64.html
+    /// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn generate_u64_between_range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        self.generate_number_between_range(*range.start() as u128, *range.end() as u128) as u64
+    }
+    /// Generate a random [`usize`] within a given [`RangeInclusive`].
+    ///
+    /// [`usize`]: https://doc.rust-lang.org/core/primitive.usize.html
+    /// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+    pub const fn generate_usize_between_range(&mut self, range: RangeInclusive<usize>) -> usize {
+        self.generate_number_between_range(*range.start() as u128, *range.end() as u128) as usize
+    }
+    /// Generate a random [`u128`] within a given [`RangeInclusive`].
+    ///
+    /// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+    /// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+    pub const fn generate_u128_between_range(&mut self, range: RangeInclusive<u128>) -> u128 {
+        self.generate_number_between_range(*range.start(), *range.end())
+    }
+    const fn generate_number_between_range(&mut self, start: u128, end: u128) -> u128 {
+        let upper_bound = end - start;
+        let bits = 128 - upper_bound.leading_zeros();
+        let mut number = self.generate_bits(bits);
+        while number > upper_bound {number = self.generate_bits(bits);}
+        number + start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prng, AcornBuilder};
+    #[test]
+    fn bits_come_from_the_top_of_each_output() {
+        let mut raw = prng();
+        let (first, second) = (raw.generate_u128(), raw.generate_u128());
+        let mut buffered = BufferedAcorn::new(prng());
+        assert_eq!(buffered.generate_bits(0), 0);
+        assert_eq!(buffered.available_bits(), 0);
+        assert_eq!(buffered.generate_bits(1), first >> 119);
+        assert_eq!(buffered.generate_bits(50), (first >> 69) & ((1 << 50) - 1));
+        assert_eq!(buffered.generate_bits(29), (((first >> 60) & ((1 << 9) - 1)) << 20) | (second >> 100));
+        assert_eq!(buffered.available_bits(), 40);
+        assert_eq!(buffered.generate_bits(40), (second >> 60) & ((1 << 40) - 1));
+        assert_eq!(buffered.into_inner(), raw);
+    }
+    #[test]
+    fn full_width_and_small_modulus() {
+        let mut buffered = BufferedAcorn::new(AcornBuilder::new().modulus(30).build());
+        let mut raw = AcornBuilder::new().modulus(30).build();
+        let expected = (0..8).fold(0, |number, _| (number << 15) | (raw.generate_u128() >> 15));
+        assert_eq!(buffered.generate_bits(200), (expected << 8) | (raw.generate_u128() >> 22)); // clamped to 128
+        assert_eq!(buffered.available_bits(), 7);
+    }
+    #[test]
+    fn cheap_narrow_ranges() {
+        let mut buffered = BufferedAcorn::new(prng());
+        let mut counts = [0_u32; 6];
+        for _ in 0..6000 {counts[usize::from(buffered.generate_u8_between_range(1..=6)) - 1] += 1;}
+        assert!(counts.iter().all(|count| (900..1100).contains(count)));
+        let mut heads = 0;
+        for _ in 0..1200 {if buffered.generate_bool() {heads += 1;}}
+        assert!((500..700).contains(&heads));
+        // 1200 coin flips and 6000 dice rolls of 3 bits with 25% rejected, 60 bits at a time, need far fewer than
+        // 7200 outputs
+        let mut raw = prng();
+        let mut outputs = 0;
+        while raw != buffered.prng {
+            raw.generate_u128();
+            outputs += 1;
+        }
+        assert!(outputs < 500);
+        assert_eq!(buffered.generate_u64_between_range(7..=7), 7);
+        assert!(buffered.generate_u128_between_range(0..=u128::MAX) > 0);
+    }
+}
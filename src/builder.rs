@@ -0,0 +1,202 @@
+use crate::{Acorn, Order, Seed, Version};
+
+/// Builder for an [Acorn](struct.Acorn.html) generator exposing every setting in one place.
+///
+/// Every value is clamped rather than rejected. Use [`clamped`] to find out which values were changed.
+///
+/// Values are clamped independently of each other and no combination is rejected, the only interaction being that
+/// a [`Version::V1`] seed is clamped against the chosen modulus. As with [`Acorn::new`], a [`Version::V1`] seed is
+/// also made odd when the generator is built, which is not reported by [`clamped`] since every odd seed below the
+/// modulus is still a distinct generator.
+///
+/// [`clamped`]: struct.AcornBuilder.html#method.clamped
+/// [`Version::V1`]: enum.Version.html#variant.V1
+/// [`Acorn::new`]: struct.Acorn.html#method.new
+#[derive(Debug, Eq, PartialEq)]
+pub struct AcornBuilder {
+    order: usize,
+    seed: u128,
+    warmup: usize,
+    modulus: u32,
+    version: Version,
+}
+
+/// Which values of an [`AcornBuilder`](struct.AcornBuilder.html) were clamped when building the generator.
+#[derive(Debug, Default, Eq, PartialEq)]
+#[allow(clippy::struct_excessive_bools)]
+pub struct Clamped {
+    /// The order was outside of 45 to 65,535.
+    pub order: bool,
+    /// The seed was below 1,000,000 or not below the modulus. This never happens with [`Version::V2`].
+    ///
+    /// [`Version::V2`]: enum.Version.html#variant.V2
+    pub seed: bool,
+    /// The warm-up length was above 65,535.
+    pub warmup: bool,
+    /// The modulus power was outside of 30 to 120.
+    pub modulus: bool,
+}
+
+impl Clamped {
+    /// Whether any value was clamped.
+    #[must_use]
+    pub const fn any(&self) -> bool {
+        self.order || self.seed || self.warmup || self.modulus
+    }
+}
+
+impl Default for AcornBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AcornBuilder {
+    /// Create a new [`AcornBuilder`](struct.AcornBuilder.html) with the same settings as [`Acorn::new`] with
+    /// the lowest [Order](struct.Order.html) and [Seed](struct.Seed.html).
+    ///
+    /// That is an order of 45, a seed of 1,000,000, a warm-up of 20 outputs, a modulus of 2^120 and [`Version::V1`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, AcornBuilder, Order, Seed};
+    ///
+    /// let prng = AcornBuilder::new().order(45).seed(1_000_000).warmup(20).modulus(120).build();
+    ///
+    /// assert_eq!(prng, Acorn::new(Order::new(45), Seed::new(1_000_000)));
+    /// ```
+    ///
+    /// [`Acorn::new`]: struct.Acorn.html#method.new
+    /// [`Version::V1`]: enum.Version.html#variant.V1
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {order: 45, seed: 1_000_000, warmup: 20, modulus: 120, version: Version::V1}
+    }
+    /// Set the order, see [`Order::new`].
+    ///
+    /// [`Order::new`]: struct.Order.html#method.new
+    #[must_use]
+    pub const fn order(mut self, order: usize) -> Self {
+        self.order = order;
+        self
+    }
+    /// Set the seed, see [`Seed::new`].
+    ///
+    /// Note that the input is kept as is until the generator is built. With [`Version::V1`] it is then raised to at
+    /// least 1,000,000, made odd and reduced below the modulus, while later versions scramble it.
+    ///
+    /// [`Seed::new`]: struct.Seed.html#method.new
+    /// [`Version::V1`]: enum.Version.html#variant.V1
+    #[must_use]
+    pub const fn seed(mut self, seed: u128) -> Self {
+        self.seed = seed;
+        self
+    }
+    /// Set the number of outputs discarded after seeding.
+    ///
+    /// Fewer outputs make construction cheaper but with small seeds the first outputs may not have passed the
+    /// modulus ceiling yet. Higher orders and small seeds may benefit from more outputs.
+    ///
+    /// Note that the input is clamped between 0 and 65,535 rather than rejected.
+    #[must_use]
+    pub const fn warmup(mut self, warmup: usize) -> Self {
+        self.warmup = warmup;
+        self
+    }
+    /// Set the modulus as a power of two, see [`Modulus::new`].
+    ///
+    /// [`Modulus::new`]: struct.Modulus.html#method.new
+    #[must_use]
+    pub const fn modulus(mut self, power: u32) -> Self {
+        self.modulus = power;
+        self
+    }
+    /// Set the stream [Version](enum.Version.html).
+    #[must_use]
+    pub const fn version(mut self, version: Version) -> Self {
+        self.version = version;
+        self
+    }
+    /// Report which values will be clamped when building the generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{AcornBuilder, Clamped};
+    ///
+    /// let builder = AcornBuilder::new().order(1).seed(2_u128.pow(100)).modulus(64);
+    ///
+    /// assert_eq!(builder.clamped(), Clamped {order: true, seed: true, warmup: false, modulus: false});
+    /// ```
+    #[must_use]
+    pub const fn clamped(&self) -> Clamped {
+        let modulus = crate::Modulus::new(self.modulus);
+        Clamped {
+            order: Order::new(self.order).get() != self.order,
+            seed: matches!(self.version, Version::V1)
+                && (self.seed < 1_000_000 || self.seed >= 2_u128.pow(modulus.bits())),
+            warmup: self.warmup > 65_535,
+            modulus: modulus.bits() != self.modulus,
+        }
+    }
+    /// Build the generator, clamping every value as reported by [`clamped`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{AcornBuilder, Version};
+    ///
+    /// let mut prng = AcornBuilder::new().seed(7).warmup(100).version(Version::V2).build();
+    /// let number = prng.generate_u8_between_range(1..=6);
+    /// ```
+    ///
+    /// [`clamped`]: struct.AcornBuilder.html#method.clamped
+    #[must_use]
+    pub const fn build(self) -> Acorn {
+        let m = 2_u128.pow(crate::Modulus::new(self.modulus).bits());
+        let warmup = if self.warmup > 65_535 {65_535} else {self.warmup};
+        Acorn::seeded(Order::new(self.order), m, Seed::new_unclamped(self.seed), self.version, warmup)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Modulus;
+    #[test]
+    fn build_defaults() {
+        assert_eq!(AcornBuilder::new().build(), Acorn::new(Order::new(45), Seed::new(1_000_000)));
+        assert_eq!(AcornBuilder::default().clamped(), Clamped::default());
+        assert!(!AcornBuilder::new().clamped().any());
+    }
+    #[test]
+    fn build_matches_constructors() {
+        assert_eq!(AcornBuilder::new().order(77).seed(123_456_789).modulus(64).build(),
+            Acorn::with_modulus(Order::new(77), Seed::new(123_456_789), Modulus::new(64)));
+        assert_eq!(AcornBuilder::new().seed(3).version(Version::V2).build(),
+            Acorn::with_version(Order::new(45), Seed::new_unclamped(3), Version::V2));
+    }
+    #[test]
+    fn build_warmup() {
+        let mut prng = AcornBuilder::new().warmup(0).build();
+        let mut index = 0;
+        while index < 20 {
+            prng.generate_u128();
+            index += 1;
+        }
+        assert_eq!(prng, AcornBuilder::new().warmup(20).build());
+        assert_ne!(AcornBuilder::new().warmup(21).build(), AcornBuilder::new().build());
+        assert_eq!(AcornBuilder::new().warmup(usize::MAX).build(), AcornBuilder::new().warmup(65_535).build());
+    }
+    #[test]
+    fn report_clamped() {
+        assert_eq!(AcornBuilder::new().order(70_000).seed(1).warmup(70_000).modulus(10).clamped(),
+            Clamped {order: true, seed: true, warmup: true, modulus: true});
+        assert!(AcornBuilder::new().seed(2_u128.pow(120)).clamped().seed);
+        assert!(!AcornBuilder::new().seed(2_u128.pow(120)).version(Version::V2).clamped().any());
+        assert!(!AcornBuilder::new().seed(1).version(Version::V2).clamped().any());
+        assert!(!AcornBuilder::new().seed(2_000_000).clamped().any());
+        assert_eq!(AcornBuilder::new().seed(2_000_000).build(), AcornBuilder::new().seed(2_000_001).build()); // made odd
+    }
+}
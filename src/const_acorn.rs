@@ -0,0 +1,195 @@
+use core::ops::RangeInclusive;
+
+use crate::source::generate_versioned_range;
+use crate::state::{step, State};
+use crate::{fallible_from_acorn, temper, Acorn, Modulus, Order, RandomSource, Seed, Version};
+
+/// An [Acorn](struct.Acorn.html) generator whose [Order](struct.Order.html) is fixed at compile time.
+///
+/// The order is checked when the generator is constructed, so an order outside 45 to [`u16::MAX`] fails to compile
+/// rather than being clamped, and the loop of every step is specialised for it. The output is identical to that of
+/// an [Acorn](struct.Acorn.html) with the same settings and the two convert into each other mid-stream.
+///
+/// The sampling methods are provided by the [`RandomSource`](trait.RandomSource.html) trait and follow the
+/// [Version](enum.Version.html) of the generator.
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::{Acorn, ConstAcorn, Order, RandomSource, Seed};
+///
+/// let mut prng = ConstAcorn::<45>::new(Seed::new(1_000_000));
+/// let mut runtime = Acorn::new(Order::new(45), Seed::new(1_000_000));
+///
+/// assert_eq!(prng.generate_u128(), runtime.generate_u128());
+/// assert_eq!(prng.generate_u8_between_range(1..=6), runtime.generate_u8_between_range(1..=6));
+/// assert_eq!(Acorn::from(prng), runtime);
+/// ```
+///
+/// ```compile_fail
+/// let prng = acorn_prng::ConstAcorn::<44>::new(acorn_prng::Seed::new(1_000_000)); // the order is too small
+/// ```
+///
+/// [`u16::MAX`]: https://doc.rust-lang.org/core/primitive.u16.html#associatedconstant.MAX
+#[derive(Debug, Eq, PartialEq)]
+pub struct ConstAcorn<const ORDER: usize> {
+    m: u128,
+    state: State<u128>,
+}
+
+impl<const ORDER: usize> ConstAcorn<ORDER> {
+    const VALID_ORDER: () = assert!(ORDER >= 45 && ORDER <= 65_535, "the order must be between 45 and 65,535");
+    /// Create a new generator with a default modulus of 2^120, see [`Acorn::new`].
+    ///
+    /// [`Acorn::new`]: struct.Acorn.html#method.new
+    #[must_use]
+    pub const fn new(seed: Seed) -> Self {
+        Self::from_acorn_unchecked(&Acorn::new(Order(ORDER), seed))
+    }
+    /// Create a new generator with a given [Modulus](struct.Modulus.html), see [`Acorn::with_modulus`].
+    ///
+    /// [`Acorn::with_modulus`]: struct.Acorn.html#method.with_modulus
+    #[must_use]
+    pub const fn with_modulus(seed: Seed, modulus: Modulus) -> Self {
+        Self::from_acorn_unchecked(&Acorn::with_modulus(Order(ORDER), seed, modulus))
+    }
+    /// Create a new generator producing the stream of a given [Version](enum.Version.html), see
+    /// [`Acorn::with_version`].
+    ///
+    /// [`Acorn::with_version`]: struct.Acorn.html#method.with_version
+    #[must_use]
+    pub const fn with_version(seed: Seed, version: Version) -> Self {
+        Self::from_acorn_unchecked(&Acorn::with_version(Order(ORDER), seed, version))
+    }
+    fallible_from_acorn! {
+        /// Convert a runtime [Acorn](struct.Acorn.html) generator, which continues from the same position.
+        ///
+        /// Returns the generator unchanged as the error if its [Order](struct.Order.html) is not `ORDER`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use acorn_prng::{AcornBuilder, ConstAcorn};
+        ///
+        /// let prng = AcornBuilder::new().order(60).warmup(5).build();
+        ///
+        /// assert!(ConstAcorn::<60>::from_acorn(prng).is_ok());
+        /// assert!(ConstAcorn::<45>::from_acorn(AcornBuilder::new().order(60).build()).is_err());
+        /// ```
+        ///
+        /// # Errors
+        ///
+        /// Returns the [Acorn](struct.Acorn.html) generator if its order differs.
+        |prng| prng.k.0 == ORDER
+    }
+    const fn from_acorn_unchecked(prng: &Acorn) -> Self {
+        let () = Self::VALID_ORDER;
+        Self {m: prng.m, state: prng.state}
+    }
+    /// Convert into a runtime [Acorn](struct.Acorn.html) generator, which continues from the same position.
+    #[must_use]
+    pub const fn into_acorn(self) -> Acorn {
+        Acorn {k: Order(ORDER), m: self.m, state: self.state}
+    }
+    /// Generate a raw [`u128`], see [`Acorn::generate_u128`].
+    ///
+    /// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+    /// [`Acorn::generate_u128`]: struct.Acorn.html#method.generate_u128
+    pub const fn generate_u128(&mut self) -> u128 {
+        let number = self.step();
+        match self.state.version {
+            Version::V1 | Version::V2 | Version::V3 => number,
+            Version::V4 => temper(number, self.m.trailing_zeros()),
+        }
+    }
+    /// The number of raw outputs generated since construction, see [`Acorn::position`].
+    ///
+    /// [`Acorn::position`]: struct.Acorn.html#method.position
+    #[must_use]
+    pub const fn position(&self) -> u128 {
+        self.state.position
+    }
+    /// The same recurrence as the runtime step with the order known to the compiler, which can unroll the loop
+    /// and reduce by masking because the modulus is a power of two.
+    const fn step(&mut self) -> u128 {
+        let mask = self.m - 1;
+        step!(&mut self.state, ORDER, |previous, current| (previous + current) & mask)
+    }
+}
+
+impl<const ORDER: usize> From<ConstAcorn<ORDER>> for Acorn {
+    fn from(prng: ConstAcorn<ORDER>) -> Self {
+        prng.into_acorn()
+    }
+}
+
+impl<const ORDER: usize> TryFrom<Acorn> for ConstAcorn<ORDER> {
+    type Error = Acorn;
+    fn try_from(prng: Acorn) -> Result<Self, Acorn> {
+        Self::from_acorn(prng)
+    }
+}
+
+impl<const ORDER: usize> RandomSource for ConstAcorn<ORDER> {
+    fn next_u128(&mut self) -> u128 {
+        self.generate_u128()
+    }
+    fn output_bits(&self) -> u32 {
+        self.m.trailing_zeros()
+    }
+    fn generate_u128_between_range(&mut self, range: RangeInclusive<u128>) -> u128 {
+        generate_versioned_range(self, self.state.version, range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AcornBuilder;
+    #[test]
+    fn identical_to_runtime_order() {
+        fn compare<const ORDER: usize>(version: Version, modulus: u32) {
+            let mut prng = ConstAcorn::<ORDER>::from_acorn(AcornBuilder::new().order(ORDER).seed(123_456_789)
+                .modulus(modulus).version(version).build()).unwrap();
+            let mut runtime = AcornBuilder::new().order(ORDER).seed(123_456_789).modulus(modulus).version(version)
+                .build();
+            for _ in 0..50 {
+                assert_eq!(prng.generate_u128(), runtime.generate_u128());
+                assert_eq!(prng.generate_u16_between_range(0..=999), runtime.generate_u16_between_range(0..=999));
+                assert_eq!(prng.generate_u128_between_range(0..=u128::MAX),
+                    runtime.generate_u128_between_range(0..=u128::MAX));
+                assert_eq!(prng.generate_fixed_length_u64(7), runtime.generate_fixed_length_u64(7));
+                assert_eq!(prng.generate_bits(100), runtime.generate_bits(100));
+            }
+            assert_eq!(prng.position(), runtime.position());
+            assert_eq!(prng.into_acorn(), runtime);
+        }
+        for version in [Version::V1, Version::V2, Version::V3, Version::V4] {
+            compare::<45>(version, 120);
+            compare::<46>(version, 30);
+            compare::<101>(version, 64);
+        }
+    }
+    #[test]
+    fn constructors_and_conversions() {
+        assert_eq!(ConstAcorn::<45>::new(Seed::new(1_000_000)).into_acorn(),
+            Acorn::new(Order::new(45), Seed::new(1_000_000)));
+        assert_eq!(ConstAcorn::<50>::with_modulus(Seed::new(7), Modulus::new(40)).into_acorn(),
+            Acorn::with_modulus(Order::new(50), Seed::new(7), Modulus::new(40)));
+        assert_eq!(ConstAcorn::<65_535>::with_version(Seed::new(7), Version::V4).into_acorn(),
+            Acorn::with_version(Order::new(65_535), Seed::new(7), Version::V4));
+        let mut runtime = Acorn::new(Order::new(47), Seed::new(1_000_000));
+        runtime.generate_u128();
+        let mut prng = ConstAcorn::<47>::try_from(runtime).unwrap();
+        assert_eq!(prng.position(), 1);
+        let mut runtime = Acorn::from(prng);
+        let mut expected = Acorn::new(Order::new(47), Seed::new(1_000_000));
+        expected.generate_u128();
+        assert_eq!(runtime.generate_u128(), expected.generate_u128());
+        runtime.reset();
+        prng = ConstAcorn::try_from(runtime).unwrap();
+        assert_eq!(prng.position(), 0);
+        let runtime = ConstAcorn::<48>::try_from(prng.into_acorn()).unwrap_err(); // returned unchanged
+        assert!(ConstAcorn::<47>::try_from(runtime).is_ok());
+    }
+}
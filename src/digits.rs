@@ -0,0 +1,212 @@
+use crate::{generate_below, Acorn, Version};
+
+/// The radix of the ASCII digits generated by [`Acorn::fill_digits`] and [`Acorn::generate_digit_string`].
+///
+/// By default leading zeros are allowed and digits above 9 are lowercase letters.
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::Radix;
+///
+/// let pin = Radix::new(10);
+/// let hex = Radix::new(16).uppercase(true).leading_zero(false);
+///
+/// assert_eq!(hex.get(), 16);
+/// assert_eq!(Radix::new(100).get(), 36);
+/// ```
+///
+/// [`Acorn::fill_digits`]: struct.Acorn.html#method.fill_digits
+/// [`Acorn::generate_digit_string`]: struct.Acorn.html#method.generate_digit_string
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Radix {
+    base: u32,
+    leading_zero: bool,
+    uppercase: bool,
+}
+
+impl Radix {
+    /// Create a new [Radix](struct.Radix.html) allowing leading zeros and using lowercase letters.
+    ///
+    /// Note that the input is clamped between 2 and 36 rather than rejected.
+    #[must_use]
+    pub const fn new(radix: u32) -> Self {
+        let radix = if radix < 2 {2} else if radix > 36 {36} else {radix};
+        Self {base: radix, leading_zero: true, uppercase: false}
+    }
+    /// Set whether the first digit may be zero. A single digit may always be zero.
+    #[must_use]
+    pub const fn leading_zero(mut self, allowed: bool) -> Self {
+        self.leading_zero = allowed;
+        self
+    }
+    /// Set whether digits above 9 are uppercase rather than lowercase letters.
+    #[must_use]
+    pub const fn uppercase(mut self, uppercase: bool) -> Self {
+        self.uppercase = uppercase;
+        self
+    }
+    /// Get the clamped radix.
+    #[must_use]
+    pub const fn get(&self) -> u32 {
+        self.base
+    }
+    const fn alphabet(self) -> &'static [u8] {
+        let digits: &[u8] = if self.uppercase {b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZ"}
+            else {b"0123456789abcdefghijklmnopqrstuvwxyz"};
+        digits.split_at(self.base as usize).0
+    }
+}
+
+impl Acorn {
+    /// Fill a buffer with random ASCII digits of a given [Radix](struct.Radix.html) and return them as a [`str`].
+    ///
+    /// Unlike [`generate_fixed_length_u128`] any number of digits can be generated and the digits are not
+    /// limited to those of a number that fits into a [`u128`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Radix, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let mut buffer = [0; 4];
+    /// let pin = prng.fill_digits(&mut buffer, Radix::new(10));
+    ///
+    /// assert_eq!("5085", pin); // assuming above input. further calls will produce different results
+    /// ```
+    /// Note that as many digits as fit into the highest half of a raw output are drawn at once, so the digits for a
+    /// buffer are not the same as those for the first part of a longer buffer.
+    ///
+    /// [`str`]: https://doc.rust-lang.org/core/primitive.str.html
+    /// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+    /// [`generate_fixed_length_u128`]: struct.Acorn.html#method.generate_fixed_length_u128
+    pub const fn fill_digits<'a>(&mut self, buffer: &'a mut [u8], radix: Radix) -> &'a str {
+        let alphabet = radix.alphabet();
+        if !radix.leading_zero && buffer.len() > 1 {
+            let (first, rest) = buffer.split_at_mut(1);
+            self.fill_from_alphabet(first, alphabet.split_at(1).1);
+            self.fill_from_alphabet(rest, alphabet);
+        } else {
+            self.fill_from_alphabet(buffer, alphabet);
+        }
+        match core::str::from_utf8(buffer) {
+            Ok(digits) => digits,
+            Err(_) => unreachable!(),
+        }
+    }
+    /// Generate an array of `N` random ASCII digits of a given [Radix](struct.Radix.html), see [`fill_digits`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Radix, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let digits = prng.generate_digit_string::<8>(Radix::new(16).uppercase(true));
+    ///
+    /// assert!(digits.iter().all(u8::is_ascii_hexdigit));
+    /// ```
+    ///
+    /// [`fill_digits`]: struct.Acorn.html#method.fill_digits
+    #[must_use]
+    pub const fn generate_digit_string<const N: usize>(&mut self, radix: Radix) -> [u8; N] {
+        let mut digits = [0; N];
+        self.fill_digits(&mut digits, radix);
+        digits
+    }
+    /// Fill a buffer with bytes drawn uniformly from a non-empty alphabet.
+    #[allow(clippy::cast_possible_truncation)]
+    pub(crate) const fn fill_from_alphabet(&mut self, buffer: &mut [u8], alphabet: &[u8]) {
+        let base = alphabet.len() as u128;
+        let mut index = 0;
+        while index < buffer.len() {
+            let (mut number, count) = self.generate_indices(base, buffer.len() - index);
+            let mut position = index + count;
+            while position > index {
+                position -= 1;
+                buffer[position] = alphabet[(number % base) as usize];
+                number /= base;
+            }
+            index += count;
+        }
+    }
+    /// Generate up to `remaining` indices below a non-zero `base` at once, as the digits of a number in that base,
+    /// returning the number and how many indices it holds.
+    ///
+    /// As many indices are drawn as fit into the highest half of a single raw output, so even the last index comes
+    /// from bits with a long period rather than from the lowest bits.
+    pub(crate) const fn generate_indices(&mut self, base: u128, remaining: usize) -> (u128, usize) {
+        let limit = 1 << (self.m.trailing_zeros() / 2);
+        let mut count = 1;
+        let mut span = base;
+        while count < remaining && span <= limit / base {
+            span *= base;
+            count += 1;
+        }
+        let upper_bound = span - 1;
+        let number = match self.state.version {
+            Version::V1 | Version::V2 => { // mask the highest bits rather than the lowest
+                generate_below!(highest upper_bound, |bits| self.generate_bits(bits))
+            },
+            Version::V3 | Version::V4 => self.generate_number_between_range(0..=upper_bound),
+        };
+        (number, count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prng, AcornBuilder};
+    #[test]
+    fn new_radix() {
+        assert_eq!(Radix::new(0).get(), 2);
+        assert_eq!(Radix::new(16).get(), 16);
+        assert_eq!(Radix::new(37).get(), 36);
+        assert_eq!(Radix::new(36).alphabet()[35], b'z');
+        assert_eq!(Radix::new(36).uppercase(true).alphabet()[35], b'Z');
+        assert_eq!(Radix::new(16).alphabet(), b"0123456789abcdef");
+    }
+    #[test]
+    fn digits_in_every_radix() {
+        let mut prng = prng();
+        for radix in 2..=36 {
+            let radix = Radix::new(radix);
+            let digits = prng.generate_digit_string::<200>(radix);
+            let text = core::str::from_utf8(&digits).unwrap();
+            assert!(u128::from_str_radix(&text[..20], radix.get()).is_ok());
+            assert!(digits.iter().all(|digit| char::from(*digit).is_digit(radix.get())));
+            assert!(!digits.iter().any(u8::is_ascii_uppercase));
+        }
+        let mut buffer = [0; 3];
+        assert_eq!(prng.fill_digits(&mut [], Radix::new(10)), "");
+        assert_eq!(prng.fill_digits(&mut buffer, Radix::new(10)).len(), 3);
+    }
+    #[test]
+    fn leading_zeros() {
+        let mut prng = prng();
+        let (mut leading, mut seen) = (0, [false; 10]);
+        for _ in 0..1000 {
+            let pin = prng.generate_digit_string::<4>(Radix::new(10));
+            if pin[0] == b'0' {leading += 1;}
+            let digit = prng.generate_digit_string::<1>(Radix::new(10).leading_zero(false));
+            seen[usize::from(digit[0] - b'0')] = true;
+            assert_ne!(prng.generate_digit_string::<2>(Radix::new(2).leading_zero(false))[0], b'0');
+        }
+        assert!((50..150).contains(&leading));
+        assert!(seen.iter().all(|seen| *seen)); // a single digit may be zero
+    }
+    #[test]
+    fn uniform_hex_digits() {
+        let mut prng = AcornBuilder::new().modulus(30).build();
+        let mut counts = [0_u32; 16];
+        for _ in 0..1000 {
+            for digit in prng.generate_digit_string::<16>(Radix::new(16).uppercase(true)) {
+                counts[char::from(digit).to_digit(16).unwrap() as usize] += 1;
+                assert!(!digit.is_ascii_lowercase());
+            }
+        }
+        assert!(counts.iter().all(|count| (900..1100).contains(count)));
+    }
+}
@@ -0,0 +1,300 @@
+use crate::{Acorn, Charset};
+
+/// An [RFC 9562](https://www.rfc-editor.org/rfc/rfc9562) UUID generated by an [Acorn](struct.Acorn.html) generator.
+///
+/// Like every other output the UUIDs are reproducible from the seed, which makes them suitable for stable test
+/// snapshots but means they are **not secret** and not guaranteed to be globally unique.
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::{Acorn, Order, Seed};
+///
+/// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+/// let uuid = prng.generate_uuid_v4();
+/// let mut buffer = [0; 36];
+///
+/// assert_eq!(uuid.version(), 4);
+/// assert_eq!(uuid.encode(&mut buffer).len(), 36);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Uuid([u8; 16]);
+
+impl Uuid {
+    /// Create the UUID from 16 bytes in big-endian order, as recorded by a [Recording](struct.Recording.html).
+    pub(crate) const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+    /// Get the 16 bytes of the UUID in big-endian order.
+    #[must_use]
+    pub const fn to_bytes(self) -> [u8; 16] {
+        self.0
+    }
+    /// Get the version, which is 4 or 7 for generated UUIDs.
+    #[must_use]
+    pub const fn version(&self) -> u8 {
+        self.0[6] >> 4
+    }
+    /// Write the UUID as 36 lowercase hyphenated ASCII characters into a buffer and return them as a [`str`].
+    ///
+    /// [`str`]: https://doc.rust-lang.org/core/primitive.str.html
+    pub const fn encode<'a>(&self, buffer: &'a mut [u8; 36]) -> &'a str {
+        const HEX: &[u8; 16] = b"0123456789abcdef";
+        let mut index = 0;
+        let mut position = 0;
+        while index < 16 {
+            if matches!(index, 4 | 6 | 8 | 10) {
+                buffer[position] = b'-';
+                position += 1;
+            }
+            buffer[position] = HEX[(self.0[index] >> 4) as usize];
+            buffer[position + 1] = HEX[(self.0[index] & 0xF) as usize];
+            position += 2;
+            index += 1;
+        }
+        match core::str::from_utf8(buffer) {
+            Ok(uuid) => uuid,
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+impl core::fmt::Display for Uuid {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.encode(&mut [0; 36]))
+    }
+}
+
+/// A [ULID](https://github.com/ulid/spec) generated by an [Acorn](struct.Acorn.html) generator.
+///
+/// Like every other output the ULIDs are reproducible from the seed, which makes them suitable for stable test
+/// snapshots but means they are **not secret** and not guaranteed to be globally unique.
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::{Acorn, Order, Seed};
+///
+/// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+/// let ulid = prng.generate_ulid(1_469_918_176_385);
+/// let mut buffer = [0; 26];
+///
+/// assert!(ulid.encode(&mut buffer).starts_with("01ARYZ6S41"));
+/// assert_eq!(ulid.timestamp(), 1_469_918_176_385);
+/// ```
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Ulid([u8; 16]);
+
+impl Ulid {
+    /// Create the ULID from 16 bytes in big-endian order, as recorded by a [Recording](struct.Recording.html).
+    pub(crate) const fn from_bytes(bytes: [u8; 16]) -> Self {
+        Self(bytes)
+    }
+    /// Get the 16 bytes of the ULID in big-endian order.
+    #[must_use]
+    pub const fn to_bytes(self) -> [u8; 16] {
+        self.0
+    }
+    /// Get the timestamp in milliseconds since the Unix epoch.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn timestamp(&self) -> u64 {
+        (u128::from_be_bytes(self.0) >> 80) as u64
+    }
+    /// Write the ULID as 26 ASCII characters of Crockford's base 32 into a buffer and return them as a [`str`].
+    ///
+    /// [`str`]: https://doc.rust-lang.org/core/primitive.str.html
+    pub const fn encode<'a>(&self, buffer: &'a mut [u8; 26]) -> &'a str {
+        const CROCKFORD: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+        let value = u128::from_be_bytes(self.0);
+        let mut index = 0;
+        while index < 26 {
+            buffer[index] = CROCKFORD[((value >> (125 - 5 * index)) & 0x1F) as usize];
+            index += 1;
+        }
+        match core::str::from_utf8(buffer) {
+            Ok(ulid) => ulid,
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+impl core::fmt::Display for Ulid {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.encode(&mut [0; 26]))
+    }
+}
+
+impl Acorn {
+    /// Generate a random version 4 [Uuid](struct.Uuid.html) from 122 random bits.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let uuid = prng.generate_uuid_v4();
+    ///
+    /// assert_eq!("4f77b457-7eba-4b86-b6fe-43c8a1ddf1c6", uuid.to_string()); // assuming above input
+    /// ```
+    pub const fn generate_uuid_v4(&mut self) -> Uuid {
+        let random = self.generate_id_bits(122);
+        let value = (random >> 74) << 80 | 0x4 << 76 | ((random >> 62) & 0xFFF) << 64 | 0b10 << 62
+            | (random & 0x3FFF_FFFF_FFFF_FFFF);
+        Uuid(value.to_be_bytes())
+    }
+    /// Generate a version 7 [Uuid](struct.Uuid.html) from a timestamp and 74 random bits.
+    ///
+    /// The timestamp is in milliseconds since the Unix epoch and is supplied by the caller, so that tests can
+    /// use fixed times. UUIDs generated for the same millisecond are not ordered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let uuid = prng.generate_uuid_v7(1_700_000_000_000);
+    ///
+    /// assert_eq!("018bcfe5-6800-74f7-9ed1-5dfaeae1b6fe", uuid.to_string()); // assuming above input
+    /// ```
+    /// Note that only the lowest 48 bits of the timestamp are used.
+    pub const fn generate_uuid_v7(&mut self, unix_millis: u64) -> Uuid {
+        let random = self.generate_id_bits(74);
+        let value = (unix_millis as u128 & 0xFFFF_FFFF_FFFF) << 80 | 0x7 << 76 | (random >> 62) << 64 | 0b10 << 62
+            | (random & 0x3FFF_FFFF_FFFF_FFFF);
+        Uuid(value.to_be_bytes())
+    }
+    /// Generate a [Ulid](struct.Ulid.html) from a timestamp and 80 random bits.
+    ///
+    /// The timestamp is in milliseconds since the Unix epoch and is supplied by the caller, so that tests can
+    /// use fixed times. ULIDs generated for the same millisecond are not ordered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let ulid = prng.generate_ulid(1_700_000_000_000);
+    ///
+    /// assert_eq!("01HF7YAT009XVV8NVYQAW6VFWG", ulid.to_string()); // assuming above input
+    /// ```
+    /// Note that only the lowest 48 bits of the timestamp are used.
+    pub const fn generate_ulid(&mut self, unix_millis: u64) -> Ulid {
+        let value = (unix_millis as u128 & 0xFFFF_FFFF_FFFF) << 80 | self.generate_id_bits(80);
+        Ulid(value.to_be_bytes())
+    }
+    /// Generate a nanoid-style ID of `N` characters from the URL-safe alphabet of [`Charset::BASE64_URL`].
+    ///
+    /// The standard nanoid length is 21 characters. Like the random bits of every other ID, the characters are taken
+    /// from the highest half of each raw output, so none of them repeats with a short period across successive IDs.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let id = prng.generate_nanoid::<21>();
+    ///
+    /// assert!(id.iter().all(|c| c.is_ascii_alphanumeric() || *c == b'-' || *c == b'_'));
+    /// ```
+    ///
+    /// [`Charset::BASE64_URL`]: struct.Charset.html#associatedconstant.BASE64_URL
+    #[allow(clippy::cast_possible_truncation)]
+    #[must_use]
+    pub const fn generate_nanoid<const N: usize>(&mut self) -> [u8; N] {
+        let alphabet = Charset::BASE64_URL.as_bytes();
+        let mut id = [0; N];
+        let mut index = 0;
+        while index < N {
+            let count = if N - index < 21 {N - index} else {21}; // 6 bits per character
+            let mut number = self.generate_id_bits(count as u32 * 6);
+            let mut position = index + count;
+            while position > index {
+                position -= 1;
+                id[position] = alphabet[(number & 63) as usize];
+                number >>= 6;
+            }
+            index += count;
+        }
+        id
+    }
+    /// Generate up to 128 random bits with [`generate_bits`], taking at most half the bits of each raw output.
+    ///
+    /// An ID takes a fixed number of outputs, so any bit taken from the lower half of an output, whose period is
+    /// short, would repeat with a short period across successive IDs. [`generate_bits`] alone fills whole outputs.
+    ///
+    /// [`generate_bits`]: struct.Acorn.html#method.generate_bits
+    const fn generate_id_bits(&mut self, bits: u32) -> u128 {
+        let half = self.m.trailing_zeros() / 2;
+        let mut number = 0;
+        let mut filled = 0;
+        while filled < bits {
+            let take = if bits - filled < half {bits - filled} else {half};
+            number = (number << take) | self.generate_bits(take);
+            filled += take;
+        }
+        number
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prng, AcornBuilder};
+    #[test]
+    fn encode_uuid() {
+        let uuid = Uuid(0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210_u128.to_be_bytes());
+        assert_eq!(uuid.encode(&mut [0; 36]), "01234567-89ab-cdef-fedc-ba9876543210");
+        assert_eq!(uuid.version(), 0xC);
+        assert_eq!(uuid.to_bytes()[15], 0x10);
+    }
+    #[test]
+    fn encode_ulid() {
+        assert_eq!(Ulid([0; 16]).encode(&mut [0; 26]), "00000000000000000000000000");
+        assert_eq!(Ulid([0xFF; 16]).encode(&mut [0; 26]), "7ZZZZZZZZZZZZZZZZZZZZZZZZZ");
+        let ulid = Ulid(((1_469_918_176_385_u128) << 80).to_be_bytes());
+        assert_eq!(ulid.encode(&mut [0; 26]), "01ARYZ6S410000000000000000");
+    }
+    #[test]
+    fn uuid_version_and_variant() {
+        let mut prng = AcornBuilder::new().modulus(30).build();
+        let mut seen = 0;
+        for _ in 0..100 {
+            let v4 = prng.generate_uuid_v4().to_bytes();
+            let v7 = prng.generate_uuid_v7(0x0123_4567_89AB_CDEF).to_bytes();
+            assert_eq!(v4[6] >> 4, 4);
+            assert_eq!(v7[6] >> 4, 7);
+            assert_eq!(v4[8] >> 6, 0b10);
+            assert_eq!(v7[8] >> 6, 0b10);
+            assert_eq!(v7[..6], [0x45, 0x67, 0x89, 0xAB, 0xCD, 0xEF]);
+            seen |= u128::from_be_bytes(v4);
+        }
+        assert_eq!(seen, !(0xB << 76) & !(0b01 << 62)); // every bit other than the version and variant bits varies
+    }
+    #[test]
+    fn nanoid_characters() {
+        let mut prng = prng();
+        let alphabet = Charset::BASE64_URL.as_bytes();
+        let mut seen = [false; 64];
+        for _ in 0..100 {
+            for byte in prng.generate_nanoid::<21>() {seen[alphabet.iter().position(|c| *c == byte).unwrap()] = true;}
+        }
+        assert!(seen.iter().all(|seen| *seen));
+        assert_eq!(prng.generate_nanoid::<50>().len(), 50);
+    }
+    #[test]
+    fn reproducible_ids() {
+        assert_eq!(prng().generate_uuid_v4(), prng().generate_uuid_v4());
+        let mut prng = prng();
+        assert_ne!(prng.generate_uuid_v4(), prng.generate_uuid_v4());
+        let (first, second) = (prng.generate_ulid(5), prng.generate_ulid(5));
+        assert_ne!(first, second);
+        assert_eq!((first.timestamp(), second.timestamp()), (5, 5));
+        assert_eq!(prng.generate_ulid(u64::MAX).timestamp(), 0xFFFF_FFFF_FFFF);
+        assert_eq!(prng.generate_nanoid::<0>(), []);
+    }
+}
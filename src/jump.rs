@@ -0,0 +1,358 @@
+use crate::{Acorn, Version, temper};
+
+/// A 2x2 matrix acting on the two state registers of an [Acorn](struct.Acorn.html) generator.
+type Matrix = [[u128; 2]; 2];
+
+const IDENTITY: Matrix = [[1, 0], [0, 1]];
+
+/// Multiply two matrices modulo a power of two `m`.
+const fn multiply(a: &Matrix, b: &Matrix, m: u128) -> Matrix {
+    let mask = m - 1;
+    let mut product = [[0; 2]; 2];
+    let mut row = 0;
+    while row < 2 {
+        let mut column = 0;
+        while column < 2 {
+            product[row][column] = a[row][0].wrapping_mul(b[0][column])
+                .wrapping_add(a[row][1].wrapping_mul(b[1][column])) & mask;
+            column += 1;
+        }
+        row += 1;
+    }
+    product
+}
+
+/// Raise a matrix to the power `n` modulo a power of two `m`.
+const fn power(matrix: &Matrix, mut n: u128, m: u128) -> Matrix {
+    let mut result = IDENTITY;
+    let mut base = *matrix;
+    while n > 0 {
+        if n & 1 == 1 {result = multiply(&result, &base, m);}
+        base = multiply(&base, &base, m);
+        n >>= 1;
+    }
+    result
+}
+
+/// Invert a matrix modulo a power of two `m`, which is only possible when its determinant is odd.
+const fn invert(matrix: &Matrix, modulus: u128) -> Option<Matrix> {
+    let mask = modulus - 1;
+    let [[a, b], [c, d]] = *matrix;
+    let determinant = a.wrapping_mul(d).wrapping_sub(b.wrapping_mul(c));
+    if determinant & 1 == 0 {return None;}
+    let mut inverse = determinant; // correct to 3 bits, every Newton step doubles the correct bits
+    let mut index = 0;
+    while index < 6 {
+        inverse = inverse.wrapping_mul(2_u128.wrapping_sub(determinant.wrapping_mul(inverse)));
+        index += 1;
+    }
+    Some([
+        [d.wrapping_mul(inverse) & mask, b.wrapping_neg().wrapping_mul(inverse) & mask],
+        [c.wrapping_neg().wrapping_mul(inverse) & mask, a.wrapping_mul(inverse) & mask],
+    ])
+}
+
+impl Acorn {
+    /// The matrix that a single step applies to the state registers.
+    const fn step_matrix(&self) -> Matrix {
+        let mut probe = Acorn::initialise(self.k, self.m, None, Version::V1, (1, 0), 0);
+        probe.step();
+        let (a, c) = probe.state.y;
+        probe.state.y = (0, 1);
+        probe.step();
+        let (b, d) = probe.state.y;
+        [[a, b], [c, d]]
+    }
+    const fn apply(&mut self, matrix: &Matrix) {
+        let mask = self.m - 1;
+        let (y0, y1) = self.state.y;
+        self.state.y = (
+            matrix[0][0].wrapping_mul(y0).wrapping_add(matrix[0][1].wrapping_mul(y1)) & mask,
+            matrix[1][0].wrapping_mul(y0).wrapping_add(matrix[1][1].wrapping_mul(y1)) & mask,
+        );
+    }
+    /// The output of the most recent step, as returned by [`generate_u128`](struct.Acorn.html#method.generate_u128).
+    const fn current_output(&self) -> u128 {
+        let number = if self.k.get().is_multiple_of(2) {self.state.y.0} else {self.state.y.1};
+        match self.state.version {
+            Version::V1 | Version::V2 | Version::V3 => number,
+            Version::V4 => temper(number, self.m.trailing_zeros()),
+        }
+    }
+    /// Whether the steps of this generator can be inverted, so that [`step_back`] and [`rewind`] also work before
+    /// the start of the stream and take the same time at any [position](struct.Acorn.html#method.position).
+    ///
+    /// Each step applies a linear map to the two state registers. With a power of two modulus the map can only be
+    /// inverted when its determinant is odd, which depends on the [Order](struct.Order.html) alone. For example the
+    /// orders 45, 46 and 49 are reversible but 47, 48 and 50 are not. Generators of the other orders rewind by
+    /// restarting the stream and jumping ahead like [`seek`], so they cannot go back before its start.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// assert!(Acorn::new(Order::new(45), Seed::new(1_000_000)).is_reversible());
+    /// assert!(!Acorn::new(Order::new(47), Seed::new(1_000_000)).is_reversible());
+    /// ```
+    ///
+    /// [`step_back`]: struct.Acorn.html#method.step_back
+    /// [`rewind`]: struct.Acorn.html#method.rewind
+    /// [`seek`]: struct.Acorn.html#method.seek
+    #[must_use]
+    pub const fn is_reversible(&self) -> bool {
+        invert(&self.step_matrix(), self.m).is_some()
+    }
+    /// Undo the most recent step and return the output that it produced.
+    ///
+    /// Calling this repeatedly returns the previous outputs in reverse order, starting with the most recent one.
+    /// Every generation method other than [`generate_u128`] may take several outputs per call, see [`position`] to
+    /// count them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let first = prng.generate_u128();
+    /// let second = prng.generate_u128();
+    ///
+    /// assert_eq!(prng.step_back(), Some(second));
+    /// assert_eq!(prng.step_back(), Some(first));
+    /// assert_eq!(prng.generate_u128(), first);
+    /// ```
+    /// Note that [`None`] is returned and the generator is left unchanged at the start of the stream when the order
+    /// is not [reversible](struct.Acorn.html#method.is_reversible). Each step back then takes a number of
+    /// operations proportional to the number of bits of the [`position`].
+    ///
+    /// [`generate_u128`]: struct.Acorn.html#method.generate_u128
+    /// [`position`]: struct.Acorn.html#method.position
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    pub const fn step_back(&mut self) -> Option<u128> {
+        let output = self.current_output();
+        if self.rewind(1) {Some(output)} else {None}
+    }
+    /// Undo the most recent `n` steps at once, in a number of operations proportional to the number of bits of `n`.
+    ///
+    /// Returns whether the generator was rewound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let first = prng.generate_u128();
+    /// for _ in 0..1_000 {prng.generate_u128();}
+    ///
+    /// assert!(prng.rewind(1_001));
+    /// assert_eq!(prng.generate_u128(), first);
+    /// ```
+    /// Note that `false` is returned and the generator is left unchanged when the order is not
+    /// [reversible](struct.Acorn.html#method.is_reversible) and `n` is greater than the [`position`].
+    ///
+    /// [`position`]: struct.Acorn.html#method.position
+    #[must_use]
+    pub const fn rewind(&mut self, n: u128) -> bool {
+        match invert(&self.step_matrix(), self.m) {
+            Some(inverse) => {
+                self.apply(&power(&inverse, n, self.m));
+                self.state.position = self.state.position.wrapping_sub(n);
+            },
+            None if n <= self.state.position => self.seek(self.state.position - n),
+            None => return false,
+        }
+        true
+    }
+    /// The number of steps taken since the generator was created, reseeded or reset.
+    ///
+    /// Every raw output is one step. Generation methods that reject unsuitable outputs take an unpredictable
+    /// number of steps, which this reports exactly. The warm-up steps taken during construction are not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// prng.generate_u128();
+    /// prng.generate_u8_between_range(0..=10);
+    ///
+    /// assert_eq!(prng.position(), 3); // assuming above input, the first number above 10 was rejected
+    /// ```
+    /// Note that the position wraps around to 0 after 2^128 steps.
+    #[must_use]
+    pub const fn position(&self) -> u128 {
+        self.state.position
+    }
+    /// Skip the next `n` steps at once, in a number of operations proportional to the number of bits of `n`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let mut expected = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// for _ in 0..1_000 {expected.generate_u128();}
+    /// prng.jump(1_000);
+    ///
+    /// assert_eq!(prng, expected);
+    /// ```
+    pub const fn jump(&mut self, n: u128) {
+        self.apply(&power(&self.step_matrix(), n, self.m));
+        self.state.position = self.state.position.wrapping_add(n);
+    }
+    /// Move to an absolute [position](struct.Acorn.html#method.position) in the current stream.
+    ///
+    /// This works for every [Order](struct.Order.html) in either direction since it restarts the stream and
+    /// jumps ahead, which is also how [`rewind`] goes back when the order is not
+    /// [reversible](struct.Acorn.html#method.is_reversible).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(48), Seed::new(1_000_000));
+    /// prng.generate_u128();
+    /// let second = prng.generate_u128();
+    /// prng.generate_u128();
+    /// prng.seek(1);
+    ///
+    /// assert_eq!(prng.generate_u128(), second);
+    /// ```
+    ///
+    /// [`rewind`]: struct.Acorn.html#method.rewind
+    pub const fn seek(&mut self, position: u128) {
+        self.reset();
+        self.jump(position);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AcornBuilder, Modulus, Order, Seed};
+    #[test]
+    fn matrix_inverse() {
+        let matrix = [[3, 5], [7, 11]]; // determinant -2 is even
+        assert_eq!(invert(&matrix, 1 << 64), None);
+        let matrix = [[3, 5], [7, 10]]; // determinant -5 is odd
+        let inverse = invert(&matrix, 1 << 120).unwrap();
+        assert_eq!(multiply(&matrix, &inverse, 1 << 120), IDENTITY);
+        assert_eq!(multiply(&inverse, &matrix, 1 << 120), IDENTITY);
+        assert_eq!(power(&matrix, 3, 1 << 30), multiply(&matrix, &multiply(&matrix, &matrix, 1 << 30), 1 << 30));
+    }
+    #[test]
+    fn step_matrix_matches_step() {
+        let mut prng = Acorn::new(Order::new(77), Seed::new(123_456_789));
+        let matrix = prng.step_matrix();
+        let mut expected = Acorn::new(Order::new(77), Seed::new(123_456_789));
+        for _ in 0..100 {
+            expected.generate_u128();
+            prng.apply(&matrix);
+            assert_eq!(prng.state.y, expected.state.y);
+        }
+    }
+    #[test]
+    fn reversible_orders() {
+        let reversible: [usize; 6] = [45, 46, 49, 51, 52, 53];
+        let irreversible: [usize; 6] = [47, 48, 50, 54, 55, 57];
+        let reversible_order = |k: &usize| Acorn::new(Order::new(*k), Seed::new(1_000_000)).is_reversible();
+        assert!(reversible.iter().all(reversible_order));
+        assert!(!irreversible.iter().any(reversible_order));
+        let mut prng = Acorn::new(Order::new(47), Seed::new(1_000_000));
+        let first = prng.generate_u128();
+        let second = prng.generate_u128();
+        assert!(!prng.rewind(3)); // before the start of the stream
+        assert_eq!(prng.current_output(), second);
+        assert_eq!((prng.step_back(), prng.step_back(), prng.step_back()), (Some(second), Some(first), None));
+        assert_eq!((prng.position(), prng.generate_u128()), (0, first));
+    }
+    #[test]
+    fn every_order_rewinds() {
+        for k in 45..=300 {
+            for modulus in [64, 120] {
+                let mut prng = AcornBuilder::new().order(k).seed(987_654_321).modulus(modulus).build();
+                let outputs: [u128; 20] = core::array::from_fn(|_| prng.generate_u128());
+                for output in outputs.iter().rev() {assert_eq!(prng.step_back(), Some(*output));}
+                for _ in 0..500 {prng.generate_u128();}
+                assert!(prng.rewind(500));
+                for output in outputs {assert_eq!(prng.generate_u128(), output);}
+            }
+        }
+    }
+    #[test]
+    fn step_back_round_trip() {
+        assert!(!AcornBuilder::new().order(47).modulus(64).build().is_reversible());
+        for k in [45, 46, 49, 100, 1_001, 1_025] {
+            for version in [Version::V1, Version::V4] {
+                let mut prng = AcornBuilder::new().order(k).seed(987_654_321).modulus(64).version(version).build();
+                assert!(prng.is_reversible());
+                let outputs: [u128; 50] = core::array::from_fn(|_| prng.generate_u128());
+                for output in outputs.iter().rev() {assert_eq!(prng.step_back(), Some(*output));}
+                for output in outputs {assert_eq!(prng.generate_u128(), output);}
+            }
+        }
+    }
+    #[test]
+    fn rewind_round_trip() {
+        for k in [45, 46, 49, 53, 255] {
+            let mut prng = Acorn::with_modulus(Order::new(k), Seed::new(1_000_000), Modulus::new(120));
+            assert!(prng.is_reversible());
+            let state = prng.state.y;
+            for _ in 0..777 {prng.generate_u128();}
+            assert!(prng.rewind(777));
+            assert_eq!(prng.state.y, state);
+            assert!(prng.rewind(0));
+            assert_eq!(prng.state.y, state);
+            assert_eq!(prng.position(), 0);
+        }
+    }
+    #[test]
+    fn position_counts_steps() {
+        let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+        assert_eq!(prng.position(), 0);
+        let mut count = 0;
+        for _ in 0..100 {
+            let before = prng.state.y;
+            prng.generate_u8_between_range(0..=200);
+            let mut replay = Acorn::new(Order::new(45), Seed::new(1_000_000));
+            replay.seek(count);
+            assert_eq!(replay.state.y, before);
+            while replay.state.y != prng.state.y {
+                replay.generate_u128();
+                count += 1;
+            }
+        }
+        assert_eq!(prng.position(), count);
+        assert!(count > 100); // some outputs were rejected
+        assert_eq!(prng.step_back().map(|_| prng.position()), Some(count - 1));
+        prng.reset();
+        assert_eq!(prng.position(), 0);
+        prng.reseed(Seed::new(2_000_000));
+        assert_eq!(prng.position(), 0);
+    }
+    #[test]
+    fn jump_and_seek() {
+        for k in [45, 47, 48, 1_000] {
+            let mut prng = AcornBuilder::new().order(k).modulus(77).build();
+            let outputs: [u128; 300] = core::array::from_fn(|_| prng.generate_u128());
+            for position in [299, 0, 150, 17] {
+                prng.seek(position);
+                assert_eq!(prng.position(), position);
+                #[allow(clippy::cast_possible_truncation)]
+                let expected = outputs[position as usize];
+                assert_eq!(prng.generate_u128(), expected);
+            }
+            prng.seek(10);
+            prng.jump(100);
+            assert_eq!((prng.position(), prng.generate_u128()), (110, outputs[110]));
+            prng.jump(u128::MAX);
+            prng.jump(1);
+            assert_eq!(prng.position(), 111); // a full cycle of the position counter
+        }
+    }
+}
@@ -0,0 +1,412 @@
+use core::ops::RangeInclusive;
+
+use crate::source::generate_versioned_range;
+use crate::state::{step, State};
+use crate::{fallible_from_acorn, temper, Acorn, Modulus, Order, RandomSource, Seed, Version};
+
+/// An [Acorn](struct.Acorn.html) generator running natively on [`u64`] for targets without fast [`u128`]
+/// arithmetic.
+///
+/// Every step adds [Order](struct.Order.html) numbers, which on 32 and 64 bit microcontrollers is several times
+/// cheaper on [`u64`] than on [`u128`]. The modulus is at most 2^64, for example 2^60 or 2^64, and the output is
+/// identical to that of an [Acorn](struct.Acorn.html) with the same [Modulus](struct.Modulus.html). Seeding and
+/// warm-up run once on the [`u128`] path.
+///
+/// The sampling methods are provided by the [`RandomSource`](trait.RandomSource.html) trait and follow the
+/// [Version](enum.Version.html) of the generator.
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::{Acorn, Acorn64, Modulus, Order, RandomSource, Seed};
+///
+/// let mut prng = Acorn64::new(Order::new(45), Seed::new(1_000_000));
+/// let mut wide = Acorn::with_modulus(Order::new(45), Seed::new(1_000_000), Modulus::new(64));
+///
+/// assert_eq!(u128::from(prng.generate_u64()), wide.generate_u128());
+/// assert_eq!(prng.generate_u8_between_range(1..=6), wide.generate_u8_between_range(1..=6));
+/// ```
+///
+/// [`u64`]: https://doc.rust-lang.org/core/primitive.u64.html
+/// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+#[derive(Debug, Eq, PartialEq)]
+pub struct Acorn64 {
+    k: Order,
+    mask: u64,
+    state: State<u64>,
+}
+
+impl Acorn64 {
+    /// Create a new generator with a modulus of 2^64.
+    ///
+    /// This produces the same stream as [`Acorn::with_modulus`] with [`Modulus::new(64)`].
+    ///
+    /// [`Acorn::with_modulus`]: struct.Acorn.html#method.with_modulus
+    /// [`Modulus::new(64)`]: struct.Modulus.html#method.new
+    #[must_use]
+    pub const fn new(k: Order, seed: Seed) -> Self {
+        Acorn64::from_acorn_unchecked(&Acorn::with_modulus(k, seed, Modulus::new(64)))
+    }
+    /// Create a new generator with a given [Modulus](struct.Modulus.html) of at most 2^64, see
+    /// [`Acorn::with_modulus`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn64, Modulus, Order, Seed};
+    ///
+    /// assert!(Acorn64::with_modulus(Order::new(45), Seed::new(7), Modulus::new(60)).is_some());
+    /// assert!(Acorn64::with_modulus(Order::new(45), Seed::new(7), Modulus::new(120)).is_none());
+    /// ```
+    /// Note that [`None`] is returned if the modulus is larger than 2^64, see [`Acorn::with_modulus`] for such
+    /// generators.
+    ///
+    /// [`Acorn::with_modulus`]: struct.Acorn.html#method.with_modulus
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    #[must_use]
+    pub const fn with_modulus(k: Order, seed: Seed, modulus: Modulus) -> Option<Self> {
+        match Acorn64::from_acorn(Acorn::with_modulus(k, seed, modulus)) {
+            Ok(prng) => Some(prng),
+            Err(_) => None,
+        }
+    }
+    fallible_from_acorn! {
+        /// Convert an [Acorn](struct.Acorn.html) generator with a modulus of at most 2^64, which continues from the
+        /// same position.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use acorn_prng::{Acorn64, AcornBuilder};
+        ///
+        /// assert!(Acorn64::from_acorn(AcornBuilder::new().modulus(60).build()).is_ok());
+        /// assert!(Acorn64::from_acorn(AcornBuilder::new().build()).is_err()); // the default modulus is 2^120
+        /// ```
+        ///
+        /// # Errors
+        ///
+        /// Returns the [Acorn](struct.Acorn.html) generator unchanged if its modulus is larger than 2^64.
+        |prng| prng.m.trailing_zeros() <= 64
+    }
+    #[allow(clippy::cast_possible_truncation)]
+    const fn from_acorn_unchecked(prng: &Acorn) -> Self {
+        let State {y, seed, version, warmup, start, position} = prng.state;
+        Self {
+            k: prng.k,
+            mask: (prng.m - 1) as u64,
+            state: State {
+                y: (y.0 as u64, y.1 as u64),
+                seed,
+                version,
+                warmup,
+                start: (start.0 as u64, start.1 as u64),
+                position,
+            },
+        }
+    }
+    /// Convert into an [Acorn](struct.Acorn.html) generator, which continues from the same position.
+    #[must_use]
+    pub const fn into_acorn(self) -> Acorn {
+        let State {y, seed, version, warmup, start, position} = self.state;
+        Acorn {
+            k: self.k,
+            m: self.mask as u128 + 1,
+            state: State {
+                y: (y.0 as u128, y.1 as u128),
+                seed,
+                version,
+                warmup,
+                start: (start.0 as u128, start.1 as u128),
+                position,
+            },
+        }
+    }
+    /// Generate a raw [`u64`], identical to [`Acorn::generate_u128`] with the same modulus.
+    ///
+    /// [`u64`]: https://doc.rust-lang.org/core/primitive.u64.html
+    /// [`Acorn::generate_u128`]: struct.Acorn.html#method.generate_u128
+    pub const fn generate_u64(&mut self) -> u64 {
+        let number = self.step();
+        match self.state.version {
+            Version::V1 | Version::V2 | Version::V3 => number,
+            Version::V4 => temper_u64(number, self.mask.count_ones()),
+        }
+    }
+    /// The number of raw outputs generated since construction, see [`Acorn::position`].
+    ///
+    /// [`Acorn::position`]: struct.Acorn.html#method.position
+    #[must_use]
+    pub const fn position(&self) -> u128 {
+        self.state.position
+    }
+    const fn step(&mut self) -> u64 {
+        let mask = self.mask;
+        step!(&mut self.state, self.k.0, |previous, current| previous.wrapping_add(current) & mask)
+    }
+}
+
+/// The same mixer as the tempering of [`Version::V4`](enum.Version.html#variant.V4) for at most 64 bits, where
+/// only the low 64 bits of each multiplier matter.
+const fn temper_u64(mut z: u64, bits: u32) -> u64 {
+    let mask = u64::MAX >> (64 - bits);
+    z ^= z >> (bits / 2);
+    z = z.wrapping_mul(0x94d0_49bb_1331_11eb) & mask;
+    z ^= z >> (bits / 2 - 3);
+    z = z.wrapping_mul(0xbf58_476d_1ce4_e5b9) & mask;
+    z ^ (z >> (bits / 2 + 1))
+}
+
+impl From<Acorn64> for Acorn {
+    fn from(prng: Acorn64) -> Self {
+        prng.into_acorn()
+    }
+}
+
+impl TryFrom<Acorn> for Acorn64 {
+    type Error = Acorn;
+    fn try_from(prng: Acorn) -> Result<Self, Acorn> {
+        Acorn64::from_acorn(prng)
+    }
+}
+
+impl RandomSource for Acorn64 {
+    fn next_u128(&mut self) -> u128 {
+        u128::from(self.generate_u64())
+    }
+    fn output_bits(&self) -> u32 {
+        self.mask.count_ones()
+    }
+    fn generate_u128_between_range(&mut self, range: RangeInclusive<u128>) -> u128 {
+        generate_versioned_range(self, self.state.version, range)
+    }
+}
+
+/// A number below 2^128 as four [`u32`] limbs, lowest first.
+///
+/// [`u32`]: https://doc.rust-lang.org/core/primitive.u32.html
+type Limbs = [u32; 4];
+
+#[allow(clippy::cast_possible_truncation)]
+const fn to_limbs(number: u128) -> Limbs {
+    [number as u32, (number >> 32) as u32, (number >> 64) as u32, (number >> 96) as u32]
+}
+
+const fn from_limbs(limbs: Limbs) -> u128 {
+    limbs[0] as u128 | (limbs[1] as u128) << 32 | (limbs[2] as u128) << 64 | (limbs[3] as u128) << 96
+}
+
+/// Add two numbers limb by limb with carries and reduce the sum by a power of two modulus.
+const fn add_limbs(a: Limbs, b: Limbs, mask: Limbs) -> Limbs {
+    let mut sum = [0; 4];
+    let mut carry = false;
+    let mut index = 0;
+    while index < 4 {
+        let (limb, first) = a[index].overflowing_add(b[index]);
+        let (limb, second) = limb.overflowing_add(carry as u32);
+        sum[index] = limb & mask[index];
+        carry = first || second;
+        index += 1;
+    }
+    sum
+}
+
+/// An [Acorn](struct.Acorn.html) generator running on [`u32`] limbs for targets without fast [`u64`] or
+/// [`u128`] arithmetic.
+///
+/// Each addition of a step is done as four [`u32`] additions with carries instead of a software [`u128`] addition
+/// and reduction. Every [Modulus](struct.Modulus.html) is supported and the output is identical to that of an
+/// [Acorn](struct.Acorn.html) with the same settings. Seeding and warm-up run once on the [`u128`] path.
+///
+/// The sampling methods are provided by the [`RandomSource`](trait.RandomSource.html) trait and follow the
+/// [Version](enum.Version.html) of the generator.
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::{Acorn, Acorn32, Order, RandomSource, Seed};
+///
+/// let mut prng = Acorn32::new(Order::new(45), Seed::new(1_000_000));
+/// let mut wide = Acorn::new(Order::new(45), Seed::new(1_000_000));
+///
+/// assert_eq!(prng.generate_u128(), wide.generate_u128());
+/// assert_eq!(prng.generate_fixed_length_u32(4), wide.generate_fixed_length_u32(4));
+/// ```
+///
+/// [`u32`]: https://doc.rust-lang.org/core/primitive.u32.html
+/// [`u64`]: https://doc.rust-lang.org/core/primitive.u64.html
+/// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+#[derive(Debug, Eq, PartialEq)]
+pub struct Acorn32 {
+    k: Order,
+    mask: Limbs,
+    state: State<Limbs>,
+}
+
+impl Acorn32 {
+    /// Create a new generator with a default modulus of 2^120, see [`Acorn::new`].
+    ///
+    /// [`Acorn::new`]: struct.Acorn.html#method.new
+    #[must_use]
+    pub const fn new(k: Order, seed: Seed) -> Self {
+        Acorn32::from_acorn(Acorn::new(k, seed))
+    }
+    /// Create a new generator with a given [Modulus](struct.Modulus.html), see [`Acorn::with_modulus`].
+    ///
+    /// [`Acorn::with_modulus`]: struct.Acorn.html#method.with_modulus
+    #[must_use]
+    pub const fn with_modulus(k: Order, seed: Seed, modulus: Modulus) -> Self {
+        Acorn32::from_acorn(Acorn::with_modulus(k, seed, modulus))
+    }
+    /// Convert an [Acorn](struct.Acorn.html) generator, which continues from the same position.
+    ///
+    /// Unlike [`Acorn64::from_acorn`] this cannot fail, since every modulus fits in the limbs.
+    ///
+    /// [`Acorn64::from_acorn`]: struct.Acorn64.html#method.from_acorn
+    #[must_use]
+    #[allow(clippy::needless_pass_by_value)] // the generator is taken over like by Acorn64::from_acorn
+    pub const fn from_acorn(prng: Acorn) -> Self {
+        let State {y, seed, version, warmup, start, position} = prng.state;
+        Self {
+            k: prng.k,
+            mask: to_limbs(prng.m - 1),
+            state: State {
+                y: (to_limbs(y.0), to_limbs(y.1)),
+                seed,
+                version,
+                warmup,
+                start: (to_limbs(start.0), to_limbs(start.1)),
+                position,
+            },
+        }
+    }
+    /// Convert into an [Acorn](struct.Acorn.html) generator, which continues from the same position.
+    #[must_use]
+    pub const fn into_acorn(self) -> Acorn {
+        let State {y, seed, version, warmup, start, position} = self.state;
+        Acorn {
+            k: self.k,
+            m: from_limbs(self.mask) + 1,
+            state: State {
+                y: (from_limbs(y.0), from_limbs(y.1)),
+                seed,
+                version,
+                warmup,
+                start: (from_limbs(start.0), from_limbs(start.1)),
+                position,
+            },
+        }
+    }
+    /// Generate a raw [`u128`], identical to [`Acorn::generate_u128`].
+    ///
+    /// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+    /// [`Acorn::generate_u128`]: struct.Acorn.html#method.generate_u128
+    pub const fn generate_u128(&mut self) -> u128 {
+        let number = from_limbs(self.step());
+        match self.state.version {
+            Version::V1 | Version::V2 | Version::V3 => number,
+            Version::V4 => temper(number, self.output_bits_const()),
+        }
+    }
+    /// The number of raw outputs generated since construction, see [`Acorn::position`].
+    ///
+    /// [`Acorn::position`]: struct.Acorn.html#method.position
+    #[must_use]
+    pub const fn position(&self) -> u128 {
+        self.state.position
+    }
+    const fn output_bits_const(&self) -> u32 {
+        self.mask[0].count_ones() + self.mask[1].count_ones() + self.mask[2].count_ones() + self.mask[3].count_ones()
+    }
+    const fn step(&mut self) -> Limbs {
+        let mask = self.mask;
+        step!(&mut self.state, self.k.0, |previous, current| add_limbs(previous, current, mask))
+    }
+}
+
+impl From<Acorn> for Acorn32 {
+    fn from(prng: Acorn) -> Self {
+        Acorn32::from_acorn(prng)
+    }
+}
+
+impl From<Acorn32> for Acorn {
+    fn from(prng: Acorn32) -> Self {
+        prng.into_acorn()
+    }
+}
+
+impl RandomSource for Acorn32 {
+    fn next_u128(&mut self) -> u128 {
+        self.generate_u128()
+    }
+    fn output_bits(&self) -> u32 {
+        self.output_bits_const()
+    }
+    fn generate_u128_between_range(&mut self, range: RangeInclusive<u128>) -> u128 {
+        generate_versioned_range(self, self.state.version, range)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AcornBuilder;
+    fn build(order: usize, modulus: u32, version: Version) -> Acorn {
+        AcornBuilder::new().order(order).seed(987_654_321).modulus(modulus).version(version).build()
+    }
+    #[test]
+    fn limb_arithmetic() {
+        let mask = to_limbs(u128::MAX >> 8);
+        assert_eq!(from_limbs(to_limbs(0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210)),
+            0x0123_4567_89AB_CDEF_FEDC_BA98_7654_3210);
+        assert_eq!(from_limbs(add_limbs(to_limbs(u128::MAX >> 8), to_limbs(1), mask)), 0);
+        assert_eq!(from_limbs(add_limbs(to_limbs(u64::MAX.into()), to_limbs(1), mask)), 1 << 64);
+        for bits in [30, 47, 64, 120] {
+            let bits = bits.min(64);
+            assert_eq!(u128::from(temper_u64(12_345, bits)), temper(12_345, bits));
+            let largest = u64::MAX >> (64 - bits);
+            assert_eq!(u128::from(temper_u64(largest, bits)), temper(u128::from(largest), bits));
+        }
+    }
+    #[test]
+    fn identical_vectors_on_every_backend() {
+        for version in [Version::V1, Version::V2, Version::V3, Version::V4] {
+            for (order, modulus) in [(45, 30), (46, 60), (50, 64), (101, 64)] {
+                let mut wide = build(order, modulus, version);
+                let mut native = Acorn64::from_acorn(build(order, modulus, version)).unwrap();
+                let mut limbs = Acorn32::from_acorn(build(order, modulus, version));
+                for _ in 0..100 {
+                    let number = wide.generate_u128();
+                    assert_eq!(u128::from(native.generate_u64()), number);
+                    assert_eq!(limbs.generate_u128(), number);
+                    let range = wide.generate_u32_between_range(1..=1_000_000);
+                    assert_eq!(native.generate_u32_between_range(1..=1_000_000), range);
+                    assert_eq!(limbs.generate_u32_between_range(1..=1_000_000), range);
+                    let number = wide.generate_u128_between_range(0..=u128::MAX - 1);
+                    assert_eq!(native.generate_u128_between_range(0..=u128::MAX - 1), number);
+                    assert_eq!(limbs.generate_u128_between_range(0..=u128::MAX - 1), number);
+                }
+                assert_eq!(native.position(), wide.position());
+                assert_eq!(native.into_acorn(), wide);
+                assert_eq!(Acorn::from(limbs), wide);
+            }
+            let mut wide = build(47, 120, version);
+            let mut limbs = Acorn32::from(build(47, 120, version));
+            for _ in 0..100 {
+                assert_eq!(limbs.generate_u128(), wide.generate_u128());
+                assert_eq!(limbs.generate_fixed_length_u64(15), wide.generate_fixed_length_u64(15));
+            }
+        }
+    }
+    #[test]
+    fn constructors_and_conversions() {
+        assert_eq!(Acorn64::new(Order::new(45), Seed::new(1_000_000)).into_acorn(),
+            Acorn::with_modulus(Order::new(45), Seed::new(1_000_000), Modulus::new(64)));
+        assert_eq!(Acorn64::with_modulus(Order::new(45), Seed::new(1_000_000), Modulus::new(120)), None);
+        assert_eq!(Acorn64::with_modulus(Order::new(45), Seed::new(7), Modulus::new(60)).unwrap().output_bits(), 60);
+        assert_eq!(Acorn32::new(Order::new(45), Seed::new(7)).into_acorn(), Acorn::new(Order::new(45), Seed::new(7)));
+        assert_eq!(Acorn32::with_modulus(Order::new(45), Seed::new(7), Modulus::new(30)).output_bits(), 30);
+        let wide = Acorn64::try_from(Acorn::new(Order::new(45), Seed::new(7))).unwrap_err();
+        assert_eq!(wide, Acorn::new(Order::new(45), Seed::new(7)));
+    }
+}
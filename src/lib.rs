@@ -10,18 +10,115 @@
 //! ## Usage
 //!
 //! Create a generator by specifying both the [Order](struct.Order.html) and the [Seed](struct.Seed.html).
+//! Optionally a [Modulus](struct.Modulus.html) other than the default 2^120 can also be specified.
 //! Allowing the user of this library to specify the starting data makes reproducability possible.
 //! If you do not require reproducable pseudo-random numbers then using the current time converted into
 //! a [`u128`](https://doc.rust-lang.org/core/primitive.u128.html) for the [Seed](struct.Seed.html) should provide
 //! sufficient randomness.
 //!
+//! ## Features
+//!
+//! The optional `std` feature adds [`Seed::from_system_time`](struct.Seed.html#method.from_system_time) for seeding
+//! without choosing a value yourself. On unix targets it also adds
+//! [`Seed::from_entropy`](struct.Seed.html#method.from_entropy) and
+//! [`Acorn::from_entropy`](struct.Acorn.html#method.from_entropy), which read `/dev/urandom`.
+//!
+//! The optional `alloc` feature, also enabled by `std`, adds
+//! [`Acorn::generate_string`](struct.Acorn.html#method.generate_string) and
+//! [`Acorn::generate_string_from_chars`](struct.Acorn.html#method.generate_string_from_chars). Without it random
+//! strings can still be written into buffers with [`Acorn::fill_string`](struct.Acorn.html#method.fill_string).
+//!
+//! The optional `critical-section` feature makes [`SharedAcorn`](struct.SharedAcorn.html) lock through the
+//! [critical-section](https://crates.io/crates/critical-section) crate, which is safe from interrupt handlers and
+//! needs no atomic instructions. Without it `SharedAcorn` is only available on targets with 8-bit atomics.
+//!
+//! Alternatively an [`AcornBuilder`](struct.AcornBuilder.html) exposes every setting in one place, including
+//! the number of outputs discarded during warm-up.
+//!
 //! Then you can generate either a number of a fixed digit length or a number bewteen a specified range (inclusive).
+//! Every primitive type implementing [`AcornUniform`](trait.AcornUniform.html) can also be generated generically
+//! with [`generate`](struct.Acorn.html#method.generate) and [`generate_range`](struct.Acorn.html#method.generate_range).
+//!
+//! When the order is fixed, a [`ConstAcorn`](struct.ConstAcorn.html) checks it at compile time and specialises the
+//! step for it while producing the same stream.
+//!
+//! On targets without fast [`u128`](https://doc.rust-lang.org/core/primitive.u128.html) arithmetic the same streams
+//! can be generated on [`u64`](https://doc.rust-lang.org/core/primitive.u64.html) with an
+//! [`Acorn64`](struct.Acorn64.html) for moduli up to 2^64, or on
+//! [`u32`](https://doc.rust-lang.org/core/primitive.u32.html) limbs with an [`Acorn32`](struct.Acorn32.html).
+//!
+//! Lookup tables such as Zobrist keys can be generated at compile time with
+//! [`acorn_const_array!`](macro.acorn_const_array.html).
+//!
+//! For many small draws such as coin flips and dice rolls wrap the generator in a
+//! [`BufferedAcorn`](struct.BufferedAcorn.html) to avoid discarding most of the bits of every output.
+//!
+//! Text can be generated without allocating as digits of any [Radix](struct.Radix.html), as strings from a
+//! [Charset](struct.Charset.html) or as [Uuid](struct.Uuid.html), [Ulid](struct.Ulid.html) and nanoid-style IDs.
+//! None of these are secret.
+//!
+//! The sampling methods are also provided by the [`RandomSource`](trait.RandomSource.html) trait on top of any
+//! source of raw outputs, so generic helpers can be tested with a [`ScriptedSource`](struct.ScriptedSource.html).
+//!
+//! To reproduce a failure, wrap the generator in a [Recording](struct.Recording.html) that logs every draw into a
+//! buffer, a [`Vec`](https://doc.rust-lang.org/alloc/vec/struct.Vec.html) with `alloc` or a file with `std`, and
+//! feed the log back with a [Replay](struct.Replay.html) to find the first draw where another run diverges.
+//!
+//! With `std`, quick scripts and tests can call [`random`](fn.random.html) or [`thread_acorn`](fn.thread_acorn.html)
+//! for a generator per thread seeded from entropy or the `ACORN_SEED` environment variable, which reports its seed.
+//!
+//! A [`SharedAcorn`](struct.SharedAcorn.html) can be kept in a `static` and used from threads, or with the
+//! `critical-section` feature from a main loop and interrupt handlers, one locked call at a time.
 //!
 //! Please see the [Acorn](struct.Acorn.html) struct documentation for examples.
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+#[cfg(any(test, feature = "std"))]
+extern crate std;
+
+mod buffered;
+mod builder;
+mod const_acorn;
+mod digits;
+mod ids;
+mod jump;
+mod lanes;
+mod record;
+#[cfg(any(feature = "critical-section", target_has_atomic = "8"))]
+mod shared;
+mod source;
+mod state;
+mod strings;
+mod tables;
+#[cfg(feature = "std")]
+mod thread;
+mod uniform;
+
+pub use buffered::BufferedAcorn;
+pub use builder::{AcornBuilder, Clamped};
+pub use const_acorn::ConstAcorn;
+pub use digits::Radix;
+pub use ids::{Ulid, Uuid};
+pub use lanes::{Acorn32, Acorn64};
+#[cfg(feature = "std")]
+pub use record::IoLog;
+pub use record::{
+    Call, Divergence, Draw, DrawLog, IntType, Mismatch, Recordable, Recording, Replay, SliceLog, ValueType,
+};
+#[cfg(any(feature = "critical-section", target_has_atomic = "8"))]
+pub use shared::SharedAcorn;
+pub use source::{RandomSource, ScriptedSource};
+pub use strings::Charset;
+#[cfg(feature = "std")]
+pub use thread::{random, thread_acorn, ThreadAcorn, SEED_VARIABLE};
+pub use uniform::{AcornFixedLength, AcornUniform};
+
+use state::{step, State};
+
 /// The order used for the ACORN algorithm.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Order(usize);
 
 impl Order {
@@ -41,10 +138,23 @@ impl Order {
     pub const fn new(input: usize) -> Self {
         Self(if input < 45 {45} else if input > 65_535 {65_535} else {input})
     }
+    /// The clamped value of this [Order](struct.Order.html).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::Order;
+    ///
+    /// assert_eq!(Order::new(1).get(), 45);
+    /// ```
+    #[must_use]
+    pub const fn get(&self) -> usize {
+        self.0
+    }
 }
 
 /// The seed used for the ACORN algorithm.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Seed(u128);
 
 impl Seed {
@@ -64,9 +174,379 @@ impl Seed {
     pub const fn new(input: u128) -> Self {
         Self(if input < 1_000_000 {1_000_000} else {input})
     }
+    /// Create a new [Seed](struct.Seed.html) by hashing an arbitrary slice of bytes.
+    ///
+    /// The bytes are hashed with the 128 bit [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/index.html)
+    /// algorithm and the hash `h` is mapped to `h % (2^120 - 1,000,000) + 1,000,000`. This algorithm is part of the
+    /// stable API and will not change across versions so the same input always produces the same [Seed](struct.Seed.html).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::Seed;
+    ///
+    /// let seed = Seed::from_bytes(b"level-1");
+    ///
+    /// assert_eq!(seed.get(), 482_858_413_384_733_607_429_335_510_444_341_051);
+    /// ```
+    /// Note that the result is always at least 1,000,000 and less than 2^120 so it is never clamped.
+    #[must_use]
+    pub const fn from_bytes(bytes: &[u8]) -> Self {
+        let mut hash: u128 = 0x6c62_272e_07bb_0142_62b8_2175_6295_c58d; // FNV-1a 128 bit offset basis
+        let mut index = 0;
+        while index < bytes.len() {
+            hash ^= bytes[index] as u128;
+            hash = hash.wrapping_mul(0x0000_0000_0100_0000_0000_0000_0000_013b); // FNV-1a 128 bit prime
+            index += 1;
+        }
+        Self(hash % (2_u128.pow(120) - 1_000_000) + 1_000_000)
+    }
+    /// Create a new [Seed](struct.Seed.html) by hashing a string slice.
+    ///
+    /// This hashes the UTF-8 bytes of the input exactly like [`from_bytes`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::Seed;
+    ///
+    /// assert_eq!(Seed::from_str_hash("level-1"), Seed::from_bytes(b"level-1"));
+    /// ```
+    ///
+    /// [`from_bytes`]: struct.Seed.html#method.from_bytes
+    #[must_use]
+    pub const fn from_str_hash(input: &str) -> Self {
+        Seed::from_bytes(input.as_bytes())
+    }
+    /// Create a new [Seed](struct.Seed.html) from 16 bytes of operating system entropy read from `/dev/urandom`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::Seed;
+    ///
+    /// let seed = Seed::from_entropy().unwrap();
+    /// println!("seed: {}", seed.get()); // record the seed to be able to reproduce this run
+    /// ```
+    /// Note that this is only available with the `std` feature on unix targets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `/dev/urandom` cannot be read, for example in a sandbox without it.
+    #[cfg(all(feature = "std", unix))]
+    pub fn from_entropy() -> std::io::Result<Self> {
+        use std::io::Read;
+        let mut bytes = [0; 16];
+        std::fs::File::open("/dev/urandom")?.read_exact(&mut bytes)?;
+        Ok(Seed::new(u128::from_le_bytes(bytes)))
+    }
+    /// Create a new [Seed](struct.Seed.html) from the nanoseconds elapsed since the unix epoch.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::Seed;
+    ///
+    /// let seed = Seed::from_system_time();
+    /// println!("seed: {}", seed.get()); // record the seed to be able to reproduce this run
+    /// ```
+    /// Note that this is only available with the `std` feature. A system clock set before the unix epoch
+    /// results in the lowest [Seed](struct.Seed.html).
+    #[cfg(feature = "std")]
+    #[must_use]
+    pub fn from_system_time() -> Self {
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH);
+        Seed::new(now.map_or(0, |duration| duration.as_nanos()))
+    }
+    /// Create a new [Seed](struct.Seed.html) without clamping the input.
+    ///
+    /// This is intended for use with [`Version::V2`] which scrambles every seed, so small inputs such as
+    /// test case indices each produce a distinct generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::Seed;
+    ///
+    /// assert_eq!(Seed::new_unclamped(1).get(), 1);
+    /// ```
+    /// Note that [`Version::V1`] still clamps the value to at least 1,000,000 when constructing a generator.
+    ///
+    /// [`Version::V1`]: enum.Version.html#variant.V1
+    /// [`Version::V2`]: enum.Version.html#variant.V2
+    #[must_use]
+    pub const fn new_unclamped(input: u128) -> Self {
+        Self(input)
+    }
+    /// The clamped value of this [Seed](struct.Seed.html).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::Seed;
+    ///
+    /// assert_eq!(Seed::new(1).get(), 1_000_000);
+    /// ```
+    #[must_use]
+    pub const fn get(&self) -> u128 {
+        self.0
+    }
 }
 
-enum NumType {
+/// The modulus used for the ACORN algorithm, expressed as a power of two.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Modulus(u32);
+
+impl Modulus {
+    /// Create a new [Modulus](struct.Modulus.html) of 2^`power` for constructing an [Acorn](struct.Acorn.html) generator.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::Modulus;
+    ///
+    /// let modulus = Modulus::new(120);
+    /// ```
+    /// Note that the input is clamped between 30 and 120 rather than rejected.
+    #[must_use]
+    pub const fn new(power: u32) -> Self {
+        Self(if power < 30 {30} else if power > 120 {120} else {power})
+    }
+    /// The number of meaningful bits in every raw output of a generator using this [Modulus](struct.Modulus.html).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::Modulus;
+    ///
+    /// assert_eq!(Modulus::new(64).bits(), 64);
+    /// assert_eq!(Modulus::new(128).bits(), 120);
+    /// ```
+    #[must_use]
+    pub const fn bits(&self) -> u32 {
+        self.0
+    }
+}
+
+/// The version of the stream produced by an [Acorn](struct.Acorn.html) generator.
+///
+/// New versions change how generators are initialised or how numbers are derived from the internal state.
+/// Older versions are kept so that previously generated streams stay reproducible.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Version {
+    /// The original stream. The [Seed](struct.Seed.html) is clamped to at least 1,000,000, forced to be odd
+    /// and reduced by the modulus, so for example adjacent seeds 1,000,000 and 1,000,001 produce the same stream.
+    /// Numbers within a range are generated by masking a single raw output to the next power of two and rejecting,
+    /// so a range wider than the modulus only ever yields numbers below the modulus.
+    #[default]
+    V1,
+    /// Every [Seed](struct.Seed.html) is scrambled by a bijective mixer onto the odd residues below 2^120 before
+    /// initialisation. Seeds below 2^119 never collide with each other (including adjacent seeds and seeds
+    /// below 1,000,000) and higher bits are folded in rather than discarded. Numbers within a range are masked and
+    /// rejected like [`Version::V1`], except that ranges wider than the modulus combine the highest bits of
+    /// several raw outputs.
+    ///
+    /// [`Version::V1`]: enum.Version.html#variant.V1
+    V2,
+    /// Seeds are scrambled like [`Version::V2`]. Numbers within a range are generated with Lemire's
+    /// multiply-and-reject method instead of masking to the next power of two and rejecting, so far fewer raw
+    /// outputs are discarded and ranges wider than the modulus combine several raw outputs.
+    ///
+    /// Seeding and range sampling are bundled rather than selected separately because a version names one frozen
+    /// stream: recording a [Seed](struct.Seed.html) and a version is enough to reproduce every draw, without a
+    /// growing matrix of settings to record and keep stable. Use [`Version::V2`] for scrambled seeds with the
+    /// original masked range sampling.
+    ///
+    /// [`Version::V2`]: enum.Version.html#variant.V2
+    V3,
+    /// Everything from [`Version::V3`] and every raw output is additionally tempered by a bijective mixer within
+    /// the modulus. Untempered outputs have very short periods in their lowest bits (bit `n` repeats every
+    /// 2^(`n`+1) outputs) so this protects code deriving numbers from the lowest bits of [`generate_u128`].
+    ///
+    /// [`Version::V3`]: enum.Version.html#variant.V3
+    /// [`generate_u128`]: struct.Acorn.html#method.generate_u128
+    V4,
+}
+
+/// Bijective splitmix style finaliser on 128 bits.
+const fn mix(mut z: u128) -> u128 {
+    z = (z ^ (z >> 64)).wrapping_mul(0xbf58_476d_1ce4_e5b9_94d0_49bb_1331_11eb);
+    z = (z ^ (z >> 59)).wrapping_mul(0x94d0_49bb_1331_11eb_bf58_476d_1ce4_e5b9);
+    z ^ (z >> 61)
+}
+
+/// Map a seed onto the odd residues below 2^120, bijectively for seeds below 2^119.
+const fn scramble(seed: u128) -> u128 {
+    let mask = 2_u128.pow(119) - 1;
+    let mut z = (seed & mask) ^ (mix(seed >> 119) & mask); // fold in the top 9 bits
+    z = (z ^ (z >> 60)).wrapping_mul(0xd6e8_feb8_6659_fd93_c2b2_ae3d_27d4_eb4f) & mask;
+    z = (z ^ (z >> 57)).wrapping_mul(0x9fb2_1c65_1e98_df25_ff51_afd7_ed55_8ccd) & mask;
+    z ^= z >> 59;
+    (z << 1) | 1
+}
+
+/// Temper a raw output of `bits` bits with a bijective mixer so that every bit depends on the highest bits.
+const fn temper(mut z: u128, bits: u32) -> u128 {
+    let mask = (1 << bits) - 1;
+    z ^= z >> (bits / 2);
+    z = z.wrapping_mul(0xbf58_476d_1ce4_e5b9_94d0_49bb_1331_11eb) & mask;
+    z ^= z >> (bits / 2 - 3);
+    z = z.wrapping_mul(0x94d0_49bb_1331_11eb_bf58_476d_1ce4_e5b9) & mask;
+    z ^ (z >> (bits / 2 + 1))
+}
+
+/// Multiply two [`u128`]s into a 256 bit product returned as its high and low halves.
+///
+/// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+const fn multiply_wide(a: u128, b: u128) -> (u128, u128) {
+    let (a_high, a_low) = (a >> 64, a & 0xffff_ffff_ffff_ffff);
+    let (b_high, b_low) = (b >> 64, b & 0xffff_ffff_ffff_ffff);
+    let low = a_low * b_low;
+    let middle_a = a_high * b_low;
+    let middle_b = a_low * b_high;
+    let middle = (low >> 64) + (middle_a & 0xffff_ffff_ffff_ffff) + (middle_b & 0xffff_ffff_ffff_ffff);
+    let high = a_high * b_high + (middle_a >> 64) + (middle_b >> 64) + (middle >> 64);
+    (high, (middle << 64) | (low & 0xffff_ffff_ffff_ffff))
+}
+
+/// Map a uniform `number` of `bits` bits onto `0..range` with Lemire's multiply-and-reject method.
+///
+/// Returns [`None`] when `number` has to be rejected to avoid bias. The division is only needed in the rare case
+/// that the low half of the product is below `range`.
+///
+/// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+pub(crate) const fn bounded(number: u128, range: u128, bits: u32) -> Option<u128> {
+    let (high, low) = multiply_wide(number, range);
+    let (result, fraction) = if bits == 128 {
+        (high, low)
+    } else {
+        ((high << (128 - bits)) | (low >> bits), low & ((1 << bits) - 1))
+    };
+    if fraction < range {
+        let threshold = if bits == 128 {range.wrapping_neg() % range} else {(1 << bits) % range};
+        if fraction < threshold {return None;}
+    }
+    Some(result)
+}
+
+/// Generate a number from zero to an upper bound from expressions that draw one raw output of `width` bits and a
+/// number of the highest bits of raw outputs.
+///
+/// Every sampling loop that combines and rejects raw outputs is written once here and shared by the inherent
+/// methods of [Acorn](struct.Acorn.html), the [`RandomSource`](trait.RandomSource.html) methods and the packed
+/// indices of strings and digits. This is a macro rather than a generic function because const fn cannot call
+/// trait methods.
+/// - `highest` takes as many highest bits as the bound has and rejects numbers above it.
+/// - `masked` masks a single raw output to the next power of two and rejects, like [`Version::V1`] and
+///   [`Version::V2`], where the latter combines raw outputs with `highest` for bounds wider than them.
+/// - `multiplied` is Lemire's multiply-and-reject method of [`Version::V3`] and later.
+/// - `versioned` is the method of a given [Version](enum.Version.html).
+macro_rules! generate_below {
+    (highest $upper_bound:expr, |$bits:ident| $generate_bits:expr) => {{
+        let upper_bound: u128 = $upper_bound;
+        let $bits = 128 - upper_bound.leading_zeros();
+        let mut number = $generate_bits;
+        while number > upper_bound {number = $generate_bits;}
+        number
+    }};
+    (masked $version:expr, $width:expr, $upper_bound:expr, || $next:expr, |$bits:ident| $generate_bits:expr) => {{
+        let upper_bound: u128 = $upper_bound;
+        if 128 - upper_bound.leading_zeros() > $width && !matches!($version, crate::Version::V1) {
+            generate_below!(highest upper_bound, |$bits| $generate_bits)
+        } else if upper_bound.is_power_of_two() {
+            $next % upper_bound
+        } else {
+            let x = if upper_bound > 1 << 127 {1 << 127} else {upper_bound.next_power_of_two()};
+            let mut number = $next % x;
+            while number > upper_bound {number = $next % x;}
+            number
+        }
+    }};
+    (multiplied $width:expr, $upper_bound:expr, || $next:expr, |$bits:ident| $generate_bits:expr) => {{
+        let upper_bound: u128 = $upper_bound;
+        let width: u32 = $width;
+        if upper_bound == u128::MAX {
+            let $bits = 128;
+            $generate_bits
+        } else {
+            let range = upper_bound + 1;
+            let combine = width < 128 && range > 1 << width; // combine raw outputs when the range is wider than them
+            loop {
+                let number = if combine {let $bits = 128; $generate_bits} else {$next};
+                if let Some(number) = crate::bounded(number, range, if combine {128} else {width}) {break number;}
+            }
+        }
+    }};
+    (versioned $version:expr, $width:expr, $upper_bound:expr, || $next:expr, |$bits:ident| $generate_bits:expr) => {
+        match $version {
+            crate::Version::V1 | crate::Version::V2 => {
+                generate_below!(masked $version, $width, $upper_bound, || $next, |$bits| $generate_bits)
+            },
+            crate::Version::V3 | crate::Version::V4 => {
+                generate_below!(multiplied $width, $upper_bound, || $next, |$bits| $generate_bits)
+            },
+        }
+    };
+}
+
+pub(crate) use generate_below;
+
+/// Define the `const fn from_acorn` of a generator that cannot hold every [Acorn](struct.Acorn.html), from the doc
+/// comment and a condition on `prng` for when it fits.
+///
+/// Conversions that can fail return the generator unchanged as the error, like their [`TryFrom`] impls, while those
+/// that cannot fail return the converted generator directly, like their [`From`] impls.
+///
+/// [`TryFrom`]: https://doc.rust-lang.org/core/convert/trait.TryFrom.html
+/// [`From`]: https://doc.rust-lang.org/core/convert/trait.From.html
+macro_rules! fallible_from_acorn {
+    ($(#[$doc:meta])* |$prng:ident| $fits:expr) => {
+        $(#[$doc])*
+        #[allow(clippy::result_large_err)] // the generator is handed back rather than dropped
+        pub const fn from_acorn($prng: Acorn) -> Result<Self, Acorn> {
+            if $fits {Ok(Self::from_acorn_unchecked(&$prng))} else {Err($prng)}
+        }
+    };
+}
+
+pub(crate) use fallible_from_acorn;
+
+/// The magnitudes within some bounds that fit a signed type, counting both signs of each, so that a signed number is
+/// drawn as a single index up to `last_index`.
+pub(crate) struct SignedBounds {
+    lower_bound: u128,
+    negative_lower: u128,
+    positive_count: u128,
+    pub(crate) last_index: u128,
+}
+
+impl SignedBounds {
+    /// Count the magnitudes between the bounds whose positive values are at most `max` and whose negative values are
+    /// at least `-max - 1`.
+    pub(crate) const fn new(lower_bound: u128, upper_bound: u128, max: u128) -> Self {
+        let positive_upper = if upper_bound > max {max} else {upper_bound};
+        let negative_lower = if lower_bound == 0 {1} else {lower_bound}; // there is no negative zero
+        let negative_upper = if upper_bound > max {max + 1} else {upper_bound};
+        let positive_count = if positive_upper < lower_bound {0} else {positive_upper - lower_bound + 1};
+        Self {lower_bound, negative_lower, positive_count, last_index: positive_count + (negative_upper - negative_lower)}
+    }
+    /// The sign and magnitude of the number at `index`, positive numbers first.
+    pub(crate) const fn magnitude(&self, index: u128) -> (bool, u128) {
+        if index < self.positive_count {
+            (false, self.lower_bound + index)
+        } else {
+            (true, self.negative_lower + index - self.positive_count)
+        }
+    }
+    /// The number at `index`.
+    #[allow(clippy::cast_possible_wrap)]
+    pub(crate) const fn value(&self, index: u128) -> i128 {
+        let (negative, magnitude) = self.magnitude(index);
+        if negative {(magnitude as i128).wrapping_neg()} else {magnitude as i128}
+    }
+}
+
+pub(crate) enum NumType {
     Usize,
     U8,
     U16,
@@ -91,13 +571,27 @@ impl NumType {
 }
 
 /// Additive Congruential Random Number (ACORN) generator.
-#[derive(Debug, Eq, PartialEq)]
+///
+/// Two generators are equal when they will produce the same stream from now on, that is when their
+/// [Order](struct.Order.html), modulus, [Version](enum.Version.html) and current state match. How they got there,
+/// such as the seed, warm-up length or position, is not compared.
+#[derive(Debug)]
 pub struct Acorn {
     k: Order,
     m: u128,
-    y: (u128, u128),
+    state: State<u128>,
+}
+
+// The seed, warm-up, start and position are bookkeeping for reseeding, resetting and jumping, so a generator that
+// reached a state by drawing equals one constructed directly in it.
+impl PartialEq for Acorn {
+    fn eq(&self, other: &Self) -> bool {
+        self.k == other.k && self.m == other.m && self.state.version == other.state.version && self.state.y == other.state.y
+    }
 }
 
+impl Eq for Acorn {}
+
 impl Acorn {
     /// Create a new ACORN generator.
     ///
@@ -106,7 +600,7 @@ impl Acorn {
     /// when the lowest values are provided for both [Order](struct.Order.html) and [Seed](struct.Seed.html).
     /// It returns an [Acorn](struct.Acorn.html) struct that you can use to generate pseudo-random numbers.
     ///
-    /// The internal modulus is fixed as a [`u128`] set to 2^120.
+    /// The internal modulus is a [`u128`] set to 2^120. Use [`with_modulus`] to specify a different one.
     ///
     /// # Examples
     ///
@@ -120,37 +614,236 @@ impl Acorn {
     /// Note that the created generator needs to be mutable to change the internal state.
     ///
     /// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+    /// [`with_modulus`]: struct.Acorn.html#method.with_modulus
     #[must_use]
-    pub const fn new(k: Order, mut seed: Seed) -> Self {
-        if seed.0.is_multiple_of(2) {seed.0 += 1} // ensure seed is odd
-        let m = 2_u128.pow(120); // set modulus to 2^120
-        seed.0 %= m; // ensure seed is less than m
-        let y = (seed.0, seed.0);
-        let mut acorn = Self {k,m,y};
+    pub const fn new(k: Order, seed: Seed) -> Self {
+        Acorn::with_modulus(k, seed, Modulus(120))
+    }
+    /// Create a new ACORN generator seeded from operating system entropy.
+    ///
+    /// This uses [`Seed::from_entropy`] and returns the [Seed](struct.Seed.html) alongside the generator
+    /// so that it can be recorded and the run reproduced with [`new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let (mut prng, seed) = Acorn::from_entropy(Order::new(45)).unwrap();
+    /// let mut replay = Acorn::new(Order::new(45), Seed::new(seed.get()));
+    ///
+    /// assert_eq!(prng.generate_u128(), replay.generate_u128());
+    /// ```
+    /// Note that this is only available with the `std` feature on unix targets.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if [`Seed::from_entropy`] fails.
+    ///
+    /// [`Seed::from_entropy`]: struct.Seed.html#method.from_entropy
+    /// [`new`]: struct.Acorn.html#method.new
+    #[cfg(all(feature = "std", unix))]
+    pub fn from_entropy(k: Order) -> std::io::Result<(Self, Seed)> {
+        let seed = Seed::from_entropy()?;
+        Ok((Acorn::new(k, seed), seed))
+    }
+    /// Create a new ACORN generator with a specified [Modulus](struct.Modulus.html).
+    ///
+    /// This behaves exactly like [`new`] except that the internal modulus is set to 2^[`bits`].
+    /// A smaller modulus produces smaller raw outputs and shorter periods. Numbers within a range wider than the
+    /// modulus combine several raw outputs from [`Version::V2`] onwards, whereas the original stream of
+    /// [`Version::V1`] never exceeds the modulus.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Modulus, Order, Seed};
+    ///
+    /// let mut prng = Acorn::with_modulus(Order::new(45), Seed::new(1_000_000), Modulus::new(64));
+    /// assert!(prng.generate_u128() < 2_u128.pow(64));
+    /// ```
+    ///
+    /// [`new`]: struct.Acorn.html#method.new
+    /// [`bits`]: struct.Modulus.html#method.bits
+    #[must_use]
+    pub const fn with_modulus(k: Order, seed: Seed, modulus: Modulus) -> Self {
+        let m = 2_u128.pow(modulus.0); // set modulus to 2^power
+        Acorn::seeded(k, m, seed, Version::V1, 20)
+    }
+    /// Create a new ACORN generator using a specific stream [Version](enum.Version.html).
+    ///
+    /// [`Version::V1`] produces exactly the same generator as [`new`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed, Version};
+    ///
+    /// let mut first = Acorn::with_version(Order::new(45), Seed::new_unclamped(1), Version::V2);
+    /// let mut second = Acorn::with_version(Order::new(45), Seed::new_unclamped(2), Version::V2);
+    ///
+    /// assert_ne!(first.generate_u128(), second.generate_u128());
+    /// ```
+    /// Note that the internal modulus is a [`u128`] set to 2^120.
+    ///
+    /// [`Version::V1`]: enum.Version.html#variant.V1
+    /// [`new`]: struct.Acorn.html#method.new
+    /// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+    #[must_use]
+    pub const fn with_version(k: Order, seed: Seed, version: Version) -> Self {
+        Acorn::seeded(k, 2_u128.pow(120), seed, version, 20)
+    }
+    /// Create a new ACORN generator with both of its registers seeded independently from a seed of any length.
+    ///
+    /// Seeding with a single [Seed](struct.Seed.html) starts both internal registers from the same value,
+    /// limiting the number of distinct starting states to 2^120. This fills each register independently by
+    /// absorbing every word of `seed` into two splitmix style 128 bit hash lanes, giving up to 2^239 distinct
+    /// starting states. Afterwards 20 [`u128`]s are cycled through exactly like [`new`].
+    ///
+    /// The generator only keeps these two 120 bit registers whatever its [Order](struct.Order.html), so 2^239 is
+    /// also the limit for a longer `seed` or a higher order: every word of `seed` still affects which of those states
+    /// is chosen.
+    ///
+    /// The internal modulus is a [`u128`] set to 2^120.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order};
+    ///
+    /// let mut prng = Acorn::with_extended_seed(Order::new(45), &[7, 1_000_000, u128::MAX]);
+    /// let number = prng.generate_u128();
+    /// ```
+    /// Note that the expansion is part of the stable API so the same `seed` always produces the same generator.
+    ///
+    /// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+    /// [`new`]: struct.Acorn.html#method.new
+    #[must_use]
+    pub const fn with_extended_seed(k: Order, seed: &[u128]) -> Self {
+        let m = 2_u128.pow(120);
+        let mut lanes: (u128, u128) = (0x9e37_79b9_7f4a_7c15_f39c_c060_5ced_c835, 0x6a09_e667_f3bc_c908_bb67_ae85_84ca_a73b);
         let mut index = 0;
-        while index < 20 { // cycle through the first 20
-            acorn.generate_u128();
+        while index < seed.len() {
+            lanes.0 = mix(lanes.0 ^ seed[index]);
+            lanes.1 = mix(lanes.1.wrapping_add(lanes.0));
             index += 1;
         }
-        acorn
+        lanes.0 = mix(lanes.0 ^ seed.len() as u128); // distinguish trailing zero words
+        lanes.1 = mix(lanes.1.wrapping_add(lanes.0));
+        let y = ((lanes.0 % m) | 1, lanes.1 % m); // the first register must be odd like a Seed
+        Acorn::initialise(k, m, None, Version::V1, y, 20)
     }
-    const fn generate_u128(&mut self) -> u128 {
-        let mut previous = self.y.0;
-        let mut current = self.y.1;
-        let mut index = 1;
-        while index < self.k.0 {
-            let result = (previous + current) % self.m;
-            if index % 2 == 0 {
-                self.y.1 = previous;
-                current = self.y.0;
-            } else {
-                self.y.0 = previous;
-                current = self.y.1;
-            }
-            previous = result;
+    pub(crate) const fn seeded(k: Order, m: u128, seed: Seed, version: Version, warmup: usize) -> Self {
+        let resolved = Acorn::resolve_seed(seed, m, version);
+        Acorn::initialise(k, m, Some(seed), version, (resolved, resolved), warmup)
+    }
+    const fn resolve_seed(Seed(seed): Seed, m: u128, version: Version) -> u128 {
+        match version {
+            Version::V1 => {
+                let mut seed = if seed < 1_000_000 {1_000_000} else {seed};
+                if seed.is_multiple_of(2) {seed += 1} // ensure seed is odd
+                seed % m // ensure seed is less than m
+            },
+            Version::V2 | Version::V3 | Version::V4 => scramble(seed) % m,
+        }
+    }
+    const fn initialise(k: Order, m: u128, seed: Option<Seed>, version: Version, y: (u128, u128), warmup: usize) -> Self {
+        let mut acorn = Self {k, m, state: State {y, seed, version, warmup, start: y, position: 0}};
+        let mut index = 0;
+        while index < warmup { // cycle through the first outputs
+            acorn.step();
             index += 1;
         }
-        if self.k.0.is_multiple_of(2) {self.y.0} else {self.y.1}
+        acorn.state.start = acorn.state.y;
+        acorn.state.position = 0;
+        acorn
+    }
+    /// Reinitialise this generator in place with a new [Seed](struct.Seed.html).
+    ///
+    /// The [Order](struct.Order.html), modulus, stream [Version](enum.Version.html) and warm-up length
+    /// are kept, so the result is identical to constructing a new generator with the same settings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// prng.reseed(Seed::new(777_777_777));
+    ///
+    /// assert_eq!(prng, Acorn::new(Order::new(45), Seed::new(777_777_777)));
+    /// ```
+    pub const fn reseed(&mut self, seed: Seed) {
+        *self = Acorn::seeded(self.k, self.m, seed, self.state.version, self.state.warmup);
+    }
+    /// Return this generator to the start of its current stream.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let first = prng.generate_u128();
+    /// prng.generate_u128();
+    /// prng.reset();
+    ///
+    /// assert_eq!(first, prng.generate_u128());
+    /// ```
+    pub const fn reset(&mut self) {
+        self.state.y = self.state.start;
+        self.state.position = 0;
+    }
+    /// The [Seed](struct.Seed.html) this generator was created or last reseeded with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let prng = Acorn::new(Order::new(45), Seed::new(1));
+    ///
+    /// assert_eq!(prng.initial_seed(), Some(Seed::new(1_000_000)));
+    /// ```
+    /// Note that this returns [`None`] for generators created with [`with_extended_seed`] since there is no single
+    /// [Seed](struct.Seed.html) to report. Record the extended seed itself to reproduce those.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    /// [`with_extended_seed`]: struct.Acorn.html#method.with_extended_seed
+    #[must_use]
+    pub const fn initial_seed(&self) -> Option<Seed> {
+        self.state.seed
+    }
+    /// Generate a raw [`u128`] directly from the ACORN algorithm.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_u128();
+    ///
+    /// assert_eq!(412_619_346_714_740_768_478_515_842_161_398_482, number); // assuming above input
+    /// ```
+    /// Note that the output is always less than the modulus so only the lowest [`bits`] bits are meaningful.
+    /// With a power of two modulus the lowest bits have very short periods (bit `n` repeats every 2^(`n`+1)
+    /// outputs) unless the output is tempered by [`Version::V4`]. Prefer [`generate_bits`] for narrow outputs.
+    ///
+    /// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+    /// [`bits`]: struct.Modulus.html#method.bits
+    /// [`Version::V4`]: enum.Version.html#variant.V4
+    /// [`generate_bits`]: struct.Acorn.html#method.generate_bits
+    pub const fn generate_u128(&mut self) -> u128 {
+        let number = self.step();
+        match self.state.version {
+            Version::V1 | Version::V2 | Version::V3 => number,
+            Version::V4 => temper(number, self.m.trailing_zeros()),
+        }
+    }
+    const fn step(&mut self) -> u128 {
+        let m = self.m;
+        step!(&mut self.state, self.k.0, |previous, current| (previous + current) % m)
     }
     /// Generate a random [`usize`] of a fixed digit length.
     ///
@@ -282,9 +975,254 @@ impl Acorn {
     pub const fn generate_fixed_length_u128(&mut self, length: usize) -> u128 {
         self.generate_fixed_length_number(length, &NumType::U128)
     }
+    /// Generate a random [`usize`] of a fixed bit length, that is with the highest of `bits` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_fixed_bit_length_usize(8);
+    ///
+    /// assert_eq!(210, number); // assuming above input. further calls will produce different results
+    /// ```
+    /// Note that the max size of a [`usize`] is platform dependant.
+    ///
+    /// [`usize`]: https://doc.rust-lang.org/core/primitive.usize.html
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn generate_fixed_bit_length_usize(&mut self, bits: u32) -> usize {
+        self.generate_fixed_bit_length_number(bits, usize::BITS) as usize
+    }
+    /// Generate a random [`u8`] of a fixed bit length, that is with the highest of `bits` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_fixed_bit_length_u8(8);
+    ///
+    /// assert_eq!(210, number); // assuming above input. further calls will produce different results
+    /// ```
+    /// Note that `bits` is clamped between 1 and 8 because [`u8::MAX`] is 8 bits long.
+    ///
+    /// [`u8`]: https://doc.rust-lang.org/core/primitive.u8.html
+    /// [`u8::MAX`]: https://doc.rust-lang.org/core/primitive.u8.html#associatedconstant.MAX
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn generate_fixed_bit_length_u8(&mut self, bits: u32) -> u8 {
+        self.generate_fixed_bit_length_number(bits, u8::BITS) as u8
+    }
+    /// Generate a random [`u16`] of a fixed bit length, that is with the highest of `bits` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_fixed_bit_length_u16(8);
+    ///
+    /// assert_eq!(210, number); // assuming above input. further calls will produce different results
+    /// ```
+    /// Note that `bits` is clamped between 1 and 16 because [`u16::MAX`] is 16 bits long.
+    ///
+    /// [`u16`]: https://doc.rust-lang.org/core/primitive.u16.html
+    /// [`u16::MAX`]: https://doc.rust-lang.org/core/primitive.u16.html#associatedconstant.MAX
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn generate_fixed_bit_length_u16(&mut self, bits: u32) -> u16 {
+        self.generate_fixed_bit_length_number(bits, u16::BITS) as u16
+    }
+    /// Generate a random [`u32`] of a fixed bit length, that is with the highest of `bits` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_fixed_bit_length_u32(8);
+    ///
+    /// assert_eq!(210, number); // assuming above input. further calls will produce different results
+    /// ```
+    /// Note that `bits` is clamped between 1 and 32 because [`u32::MAX`] is 32 bits long.
+    ///
+    /// [`u32`]: https://doc.rust-lang.org/core/primitive.u32.html
+    /// [`u32::MAX`]: https://doc.rust-lang.org/core/primitive.u32.html#associatedconstant.MAX
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn generate_fixed_bit_length_u32(&mut self, bits: u32) -> u32 {
+        self.generate_fixed_bit_length_number(bits, u32::BITS) as u32
+    }
+    /// Generate a random [`u64`] of a fixed bit length, that is with the highest of `bits` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_fixed_bit_length_u64(8);
+    ///
+    /// assert_eq!(210, number); // assuming above input. further calls will produce different results
+    /// ```
+    /// Note that `bits` is clamped between 1 and 64 because [`u64::MAX`] is 64 bits long.
+    ///
+    /// [`u64`]: https://doc.rust-lang.org/core/primitive.u64.html
+    /// [`u64::MAX`]: https://doc.rust-lang.org/core/primitive.u64.html#associatedconstant.MAX
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn generate_fixed_bit_length_u64(&mut self, bits: u32) -> u64 {
+        self.generate_fixed_bit_length_number(bits, u64::BITS) as u64
+    }
+    /// Generate a random [`u128`] of a fixed bit length, that is with the highest of `bits` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_fixed_bit_length_u128(8);
+    ///
+    /// assert_eq!(210, number); // assuming above input. further calls will produce different results
+    /// ```
+    /// Note that `bits` is clamped between 1 and 128 because [`u128::MAX`] is 128 bits long. From [`Version::V2`]
+    /// onwards bit lengths above the modulus combine the highest bits of several raw outputs so every number of
+    /// that length is equally likely, whereas the original stream of [`Version::V1`] draws from a single raw output.
+    ///
+    /// [`Version::V1`]: enum.Version.html#variant.V1
+    /// [`Version::V2`]: enum.Version.html#variant.V2
+    /// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+    /// [`u128::MAX`]: https://doc.rust-lang.org/core/primitive.u128.html#associatedconstant.MAX
+    pub const fn generate_fixed_bit_length_u128(&mut self, bits: u32) -> u128 {
+        self.generate_fixed_bit_length_number(bits, u128::BITS)
+    }
+    /// Generate a random [`isize`] of a fixed bit length, that is with the highest of `bits` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_fixed_bit_length_isize(8);
+    ///
+    /// assert_eq!(-210, number); // assuming above input. further calls will produce different results
+    /// ```
+    /// Note that the max size of an [`isize`] is platform dependant. The bit length of a negative number is that
+    /// of its absolute value.
+    ///
+    /// [`isize`]: https://doc.rust-lang.org/core/primitive.isize.html
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn generate_fixed_bit_length_isize(&mut self, bits: u32) -> isize {
+        self.generate_fixed_bit_length_signed(bits, isize::MAX as u128) as isize
+    }
+    /// Generate a random [`i8`] of a fixed bit length, that is with the highest of `bits` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_fixed_bit_length_i8(7);
+    ///
+    /// assert_eq!(-82, number); // assuming above input. further calls will produce different results
+    /// ```
+    /// Note that `bits` is clamped between 1 and 8. The bit length of a negative number is that of its absolute
+    /// value, so the only number with 8 bits is [`i8::MIN`].
+    ///
+    /// [`i8`]: https://doc.rust-lang.org/core/primitive.i8.html
+    /// [`i8::MIN`]: https://doc.rust-lang.org/core/primitive.i8.html#associatedconstant.MIN
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn generate_fixed_bit_length_i8(&mut self, bits: u32) -> i8 {
+        self.generate_fixed_bit_length_signed(bits, i8::MAX as u128) as i8
+    }
+    /// Generate a random [`i16`] of a fixed bit length, that is with the highest of `bits` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_fixed_bit_length_i16(8);
+    ///
+    /// assert_eq!(-210, number); // assuming above input. further calls will produce different results
+    /// ```
+    /// Note that `bits` is clamped between 1 and 16. The bit length of a negative number is that of its absolute
+    /// value, so the only number with 16 bits is [`i16::MIN`].
+    ///
+    /// [`i16`]: https://doc.rust-lang.org/core/primitive.i16.html
+    /// [`i16::MIN`]: https://doc.rust-lang.org/core/primitive.i16.html#associatedconstant.MIN
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn generate_fixed_bit_length_i16(&mut self, bits: u32) -> i16 {
+        self.generate_fixed_bit_length_signed(bits, i16::MAX as u128) as i16
+    }
+    /// Generate a random [`i32`] of a fixed bit length, that is with the highest of `bits` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_fixed_bit_length_i32(8);
+    ///
+    /// assert_eq!(-210, number); // assuming above input. further calls will produce different results
+    /// ```
+    /// Note that `bits` is clamped between 1 and 32. The bit length of a negative number is that of its absolute
+    /// value, so the only number with 32 bits is [`i32::MIN`].
+    ///
+    /// [`i32`]: https://doc.rust-lang.org/core/primitive.i32.html
+    /// [`i32::MIN`]: https://doc.rust-lang.org/core/primitive.i32.html#associatedconstant.MIN
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn generate_fixed_bit_length_i32(&mut self, bits: u32) -> i32 {
+        self.generate_fixed_bit_length_signed(bits, i32::MAX as u128) as i32
+    }
+    /// Generate a random [`i64`] of a fixed bit length, that is with the highest of `bits` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_fixed_bit_length_i64(8);
+    ///
+    /// assert_eq!(-210, number); // assuming above input. further calls will produce different results
+    /// ```
+    /// Note that `bits` is clamped between 1 and 64. The bit length of a negative number is that of its absolute
+    /// value, so the only number with 64 bits is [`i64::MIN`].
+    ///
+    /// [`i64`]: https://doc.rust-lang.org/core/primitive.i64.html
+    /// [`i64::MIN`]: https://doc.rust-lang.org/core/primitive.i64.html#associatedconstant.MIN
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn generate_fixed_bit_length_i64(&mut self, bits: u32) -> i64 {
+        self.generate_fixed_bit_length_signed(bits, i64::MAX as u128) as i64
+    }
+    /// Generate a random [`i128`] of a fixed bit length, that is with the highest of `bits` bits set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_fixed_bit_length_i128(8);
+    ///
+    /// assert_eq!(-210, number); // assuming above input. further calls will produce different results
+    /// ```
+    /// Note that `bits` is clamped between 1 and 128. The bit length of a negative number is that of its absolute
+    /// value, so the only number with 128 bits is [`i128::MIN`].
+    ///
+    /// [`i128`]: https://doc.rust-lang.org/core/primitive.i128.html
+    /// [`i128::MIN`]: https://doc.rust-lang.org/core/primitive.i128.html#associatedconstant.MIN
+    pub const fn generate_fixed_bit_length_i128(&mut self, bits: u32) -> i128 {
+        self.generate_fixed_bit_length_signed(bits, i128::MAX as u128)
+    }
     const fn generate_fixed_length_number(&mut self, length: usize, num_type: &NumType) -> u128 {
-        let length = if length == 0 {1} else if length > 39 {39} else {length};
-        let (lower_bound, upper_bound) = Acorn::generate_bounds(length, num_type);
+        let (lower_bound, upper_bound) = Acorn::generate_clamped_bounds(length, 39, num_type);
         self.generate_number_between_range(lower_bound..=upper_bound)
     }
     /// Generate a random [`usize`] within a given [`RangeInclusive`].
@@ -415,18 +1353,69 @@ impl Acorn {
         self.generate_number_between_range(range)
     }
     const fn generate_from_zero_range(&mut self, upper_bound: u128) -> u128 {
-        if upper_bound.is_power_of_two() {return self.generate_u128() % upper_bound;}
-        let x = if upper_bound > 2_u128.pow(127) {2_u128.pow(127)} else {upper_bound.next_power_of_two()};
-        let mut number = self.generate_u128() % x;
-        while number > upper_bound {
-            number = self.generate_u128() % x;
+        generate_below!(versioned self.state.version, self.m.trailing_zeros(), upper_bound, || self.generate_u128(),
+            |bits| self.generate_bits(bits))
+    }
+    pub(crate) const fn generate_number_between_range(&mut self, range: core::ops::RangeInclusive<u128>) -> u128 {
+        self.generate_from_zero_range(*range.end() - *range.start()) + *range.start()
+    }
+    /// Generate a random number of up to 128 `bits` taken from the highest bits of as many raw outputs as needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_bits(8);
+    ///
+    /// assert_eq!(79, number); // assuming above input. further calls will produce different results
+    /// ```
+    /// Note that `bits` is clamped to at most 128. The highest bits of each raw output have far longer periods
+    /// than the lowest bits so this is the preferred way to derive narrow numbers.
+    pub const fn generate_bits(&mut self, bits: u32) -> u128 {
+        let bits = if bits > 128 {128} else {bits};
+        let width = self.m.trailing_zeros();
+        let mut number = 0;
+        let mut filled = 0;
+        while filled < bits {
+            let take = if bits - filled < width {bits - filled} else {width};
+            number = (number << take) | (self.generate_u128() >> (width - take));
+            filled += take;
         }
         number
     }
-    const fn generate_number_between_range(&mut self, range: core::ops::RangeInclusive<u128>) -> u128 {
-        self.generate_from_zero_range(*range.end() - *range.start()) + *range.start()
+    /// Generate the sign and magnitude of a signed number of a fixed digit length whose positive values are at most
+    /// `max` and whose negative values are at least `-max - 1`.
+    pub(crate) const fn generate_fixed_length_signed(&mut self, length: usize, max_length: usize, max: u128)
+        -> (bool, u128) {
+        let (lower_bound, upper_bound) = Acorn::generate_clamped_bounds(length, max_length, &NumType::U128);
+        let signed = SignedBounds::new(lower_bound, upper_bound, max);
+        signed.magnitude(self.generate_number_between_range(0..=signed.last_index))
+    }
+    /// Generate a signed number whose absolute value has a fixed bit length and whose positive values are at most
+    /// `max` and whose negative values are at least `-max - 1`.
+    const fn generate_fixed_bit_length_signed(&mut self, bits: u32, max: u128) -> i128 {
+        let (lower_bound, upper_bound) = Acorn::generate_clamped_bit_bounds(bits, 129 - max.leading_zeros());
+        let signed = SignedBounds::new(lower_bound, upper_bound, max);
+        signed.value(self.generate_number_between_range(0..=signed.last_index))
+    }
+    const fn generate_fixed_bit_length_number(&mut self, bits: u32, max_bits: u32) -> u128 {
+        let (lower_bound, upper_bound) = Acorn::generate_clamped_bit_bounds(bits, max_bits);
+        self.generate_number_between_range(lower_bound..=upper_bound)
+    }
+    /// The bounds of the numbers of `bits` bits, clamped between 1 and `max_bits`.
+    pub(crate) const fn generate_clamped_bit_bounds(bits: u32, max_bits: u32) -> (u128, u128) {
+        Acorn::generate_bit_bounds(if bits == 0 {1} else if bits > max_bits {max_bits} else {bits})
     }
-    const fn generate_bounds(length: usize, num_type: &NumType) -> (u128, u128) {
+    pub(crate) const fn generate_bit_bounds(bits: u32) -> (u128, u128) {
+        (1 << (bits - 1), u128::MAX >> (128 - bits))
+    }
+    /// The bounds of the numbers of `length` digits, clamped between 1 and `max_length`.
+    pub(crate) const fn generate_clamped_bounds(length: usize, max_length: usize, num_type: &NumType) -> (u128, u128) {
+        Acorn::generate_bounds(if length == 0 {1} else if length > max_length {max_length} else {length}, num_type)
+    }
+    pub(crate) const fn generate_bounds(length: usize, num_type: &NumType) -> (u128, u128) {
         match length {
             1 => (0, 9),
             2 => (10, 99),
@@ -473,10 +1462,82 @@ impl Acorn {
     }
 }
 
+macro_rules! impl_try_fixed_bit_length {
+    ($($t:ty => $try_fixed_bit_length:ident, $fixed_bit_length:ident;)*) => {
+        impl Acorn {$(
+            #[doc = concat!("Generate a random [`", stringify!($t), "`] of a fixed bit length like [`", stringify!($fixed_bit_length), "`], returning [`None`] rather than clamping when `bits` is 0 or above [`", stringify!($t), "::BITS`].")]
+            ///
+            /// # Examples
+            ///
+            /// ```
+            /// use acorn_prng::{Acorn, Order, Seed};
+            ///
+            /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+            ///
+            #[doc = concat!("assert!(prng.", stringify!($try_fixed_bit_length), "(0).is_none());")]
+            #[doc = concat!("assert!(prng.", stringify!($try_fixed_bit_length), "(", stringify!($t), "::BITS + 1).is_none());")]
+            #[doc = concat!("assert!(prng.", stringify!($try_fixed_bit_length), "(1).is_some());")]
+            /// ```
+            ///
+            #[doc = concat!("[`", stringify!($t), "`]: https://doc.rust-lang.org/core/primitive.", stringify!($t), ".html")]
+            #[doc = concat!("[`", stringify!($fixed_bit_length), "`]: struct.Acorn.html#method.", stringify!($fixed_bit_length))]
+            /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+            #[doc = concat!("[`", stringify!($t), "::BITS`]: https://doc.rust-lang.org/core/primitive.", stringify!($t), ".html#associatedconstant.BITS")]
+            pub const fn $try_fixed_bit_length(&mut self, bits: u32) -> Option<$t> {
+                if bits == 0 || bits > <$t>::BITS {return None;}
+                Some(self.$fixed_bit_length(bits))
+            }
+        )*}
+    };
+}
+
+impl_try_fixed_bit_length! {
+    usize => try_generate_fixed_bit_length_usize, generate_fixed_bit_length_usize;
+    u8 => try_generate_fixed_bit_length_u8, generate_fixed_bit_length_u8;
+    u16 => try_generate_fixed_bit_length_u16, generate_fixed_bit_length_u16;
+    u32 => try_generate_fixed_bit_length_u32, generate_fixed_bit_length_u32;
+    u64 => try_generate_fixed_bit_length_u64, generate_fixed_bit_length_u64;
+    u128 => try_generate_fixed_bit_length_u128, generate_fixed_bit_length_u128;
+    isize => try_generate_fixed_bit_length_isize, generate_fixed_bit_length_isize;
+    i8 => try_generate_fixed_bit_length_i8, generate_fixed_bit_length_i8;
+    i16 => try_generate_fixed_bit_length_i16, generate_fixed_bit_length_i16;
+    i32 => try_generate_fixed_bit_length_i32, generate_fixed_bit_length_i32;
+    i64 => try_generate_fixed_bit_length_i64, generate_fixed_bit_length_i64;
+    i128 => try_generate_fixed_bit_length_i128, generate_fixed_bit_length_i128;
+}
+
+/// The generator of the unit tests of every module.
+#[cfg(test)]
+pub(crate) const fn prng() -> Acorn {
+    Acorn::new(Order::new(45), Seed::new(1_000_000))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     #[test]
+    fn last_draws_are_not_periodic() {
+        let in_base64 = |byte| Charset::BASE64_URL.as_bytes().iter().position(|c| *c == byte).unwrap() % 2 == 1;
+        let (mut original, mut lemire) = (prng(), AcornBuilder::new().version(Version::V3).build());
+        let (mut strings, mut chars, mut ids, mut buffered) = (prng(), prng(), prng(), BufferedAcorn::new(prng()));
+        let cases: [(&str, &mut dyn FnMut() -> bool); 6] = [
+            ("digits V1", &mut || original.generate_digit_string::<60>(Radix::new(2))[59] == b'1'),
+            ("digits V3", &mut || lemire.generate_digit_string::<60>(Radix::new(2))[59] == b'1'),
+            ("strings", &mut || in_base64(strings.generate_string_array::<10>(Charset::BASE64_URL)[9])),
+            ("chars", &mut || {
+                let mut coins = ['h'; 60];
+                chars.fill_chars(&mut coins, &['h', 't']);
+                coins[59] == 't'
+            }),
+            ("nanoids", &mut || in_base64(ids.generate_nanoid::<21>()[20])),
+            ("buffered coins", &mut || (0..120).fold(false, |_, _| buffered.generate_bool())),
+        ];
+        for (name, last_draw) in cases {
+            let last: [bool; 1024] = core::array::from_fn(|_| last_draw());
+            assert!((1..=256).all(|period| last.iter().zip(&last[period..]).any(|(a, b)| a != b)), "{name}");
+        }
+    }
+    #[test]
     fn new_order() {
         assert_eq!(Order::new(1), Order(45));
         assert_eq!(Order::new(77), Order(77));
@@ -489,16 +1550,211 @@ mod tests {
         assert_eq!(Seed::new(u128::MAX), Seed(340_282_366_920_938_463_463_374_607_431_768_211_455));
     }
     #[test]
+    fn seed_from_bytes() {
+        assert_eq!(Seed::from_bytes(b""), Seed(509_639_752_998_901_322_884_467_156_894_153_421));
+        assert_eq!(Seed::from_bytes(b"a"), Seed(211_817_556_832_930_921_647_599_021_524_395_044));
+        assert_eq!(Seed::from_str_hash("foobar"), Seed(322_376_420_121_063_553_161_448_927_215_298_136));
+        for index in 0..=u16::MAX {
+            let seed = Seed::from_bytes(&index.to_le_bytes());
+            assert!(seed.0 >= 1_000_000 && seed.0 < 2_u128.pow(120));
+        }
+    }
+    #[cfg(feature = "std")]
+    #[test]
+    fn seed_from_system_time() {
+        assert!(Seed::from_system_time().0 > 1_000_000);
+    }
+    #[cfg(all(feature = "std", unix))]
+    #[test]
+    fn seed_from_entropy() {
+        let (first, second) = (Seed::from_entropy().unwrap(), Seed::from_entropy().unwrap());
+        assert_ne!(first, second);
+        let (mut prng, seed) = Acorn::from_entropy(Order::new(45)).unwrap();
+        let mut replay = Acorn::new(Order::new(45), seed);
+        assert_eq!(prng.generate_u128(), replay.generate_u128());
+    }
+    #[test]
+    fn new_modulus() {
+        assert_eq!(Modulus::new(1), Modulus(30));
+        assert_eq!(Modulus::new(64), Modulus(64));
+        assert_eq!(Modulus::new(128), Modulus(120));
+    }
+    #[test]
     fn new_acorn() {
         assert_eq!(Acorn::new(Order::new(45), Seed::new(1_000_000)),
             Acorn {
                 k: Order(45),
                 m: 2_u128.pow(120),
-                y: (342_762_265_511_427_745_152_749_671_827_211_337, 942_176_506_049_466_623_853_234_760_970_194_013),
+                state: State {
+                    y: (342_762_265_511_427_745_152_749_671_827_211_337,
+                        942_176_506_049_466_623_853_234_760_970_194_013),
+                    seed: Some(Seed(1_000_000)),
+                    version: Version::V1,
+                    warmup: 20,
+                    start: (342_762_265_511_427_745_152_749_671_827_211_337,
+                        942_176_506_049_466_623_853_234_760_970_194_013),
+                    position: 0,
+                },
             }
         );
     }
     #[test]
+    fn new_acorn_with_default_modulus() {
+        assert_eq!(Acorn::with_modulus(Order::new(45), Seed::new(1_000_000), Modulus::new(120)),
+            Acorn::new(Order::new(45), Seed::new(1_000_000)));
+    }
+    #[test]
+    fn new_u128_with_modulus() {
+        let mut prng = Acorn::with_modulus(Order::new(45), Seed::new(1_000_000), Modulus::new(30));
+        for _ in 0..100 {assert!(prng.generate_u128() < 2_u128.pow(30));}
+    }
+    #[test]
+    fn new_acorn_with_extended_seed() {
+        let prng = Acorn::with_extended_seed(Order::new(45), &[1_000_000]);
+        assert_ne!(prng, Acorn::new(Order::new(45), Seed::new(1_000_000)));
+        assert_ne!(Acorn::with_extended_seed(Order::new(45), &[]), Acorn::with_extended_seed(Order::new(45), &[0]));
+        assert_ne!(Acorn::with_extended_seed(Order::new(45), &[1, 2]), Acorn::with_extended_seed(Order::new(45), &[2, 1]));
+        let mut starts = [(0, 0); 500];
+        for (index, start) in starts.iter_mut().enumerate() {
+            *start = Acorn::with_extended_seed(Order::new(45), &[0, index as u128]).state.y;
+        }
+        starts.sort_unstable();
+        assert!(starts.windows(2).all(|pair| pair[0] != pair[1]));
+    }
+    #[test]
+    fn new_acorn_with_version() {
+        assert_eq!(Acorn::with_version(Order::new(45), Seed::new(1_000_000), Version::V1),
+            Acorn::new(Order::new(45), Seed::new(1_000_000)));
+        assert_eq!(Acorn::new(Order::new(45), Seed::new_unclamped(7)), Acorn::new(Order::new(45), Seed::new(7)));
+        let v2 = |seed| Acorn::with_version(Order::new(45), Seed::new_unclamped(seed), Version::V2);
+        assert_ne!(v2(1_000_000), v2(1_000_001));
+        assert_ne!(v2(0), v2(1));
+        assert_ne!(v2(5), v2(5 + 2_u128.pow(120)));
+        let mut starts: std::vec::Vec<_> = (0..2000).chain(1_000_000..1_002_000).map(|seed| v2(seed).state.y).collect();
+        starts.sort_unstable();
+        starts.dedup();
+        assert_eq!(starts.len(), 4000);
+    }
+    #[test]
+    fn scramble_collisions() {
+        let ranges = [0..200_000, 1_000_000..1_200_000, 2_u128.pow(119) - 100_000..2_u128.pow(119) + 100_000];
+        let mut seeds: std::vec::Vec<u128> = ranges.into_iter().flatten().map(scramble).collect();
+        assert!(seeds.iter().all(|seed| seed % 2 == 1 && *seed < 2_u128.pow(120)));
+        seeds.sort_unstable();
+        seeds.dedup();
+        assert_eq!(seeds.len(), 600_000);
+        let high: std::vec::Vec<u128> = (0..512).map(|high| scramble((high << 119) | 0x3039)).collect();
+        assert!(high.iter().enumerate().all(|(index, seed)| !high[index + 1..].contains(seed)));
+    }
+    #[test]
+    fn reseed_and_reset() {
+        let mut prng = AcornBuilder::new().seed(5).warmup(7).version(Version::V2).build();
+        let first = [prng.generate_u128(), prng.generate_u128()];
+        prng.reset();
+        assert_eq!([prng.generate_u128(), prng.generate_u128()], first);
+        prng.reseed(Seed::new_unclamped(6));
+        assert_eq!(prng, AcornBuilder::new().seed(6).warmup(7).version(Version::V2).build());
+        assert_eq!(prng.initial_seed(), Some(Seed(6)));
+        prng.reseed(Seed::new_unclamped(5));
+        assert_eq!(prng.generate_u128(), first[0]);
+        let mut extended = Acorn::with_extended_seed(Order::new(45), &[1, 2, 3]);
+        assert_eq!(extended.initial_seed(), None);
+        let first = extended.generate_u128();
+        extended.reset();
+        assert_eq!(extended.generate_u128(), first);
+    }
+    #[test]
+    fn multiply_wide_products() {
+        assert_eq!(multiply_wide(0, u128::MAX), (0, 0));
+        assert_eq!(multiply_wide(u128::MAX, u128::MAX), (u128::MAX - 1, 1));
+        assert_eq!(multiply_wide(1 << 64, 1 << 64), (1, 0));
+        assert_eq!(multiply_wide(3 << 126, 4), (3, 0));
+        assert_eq!(multiply_wide(0xdead_beef, 0xcafe_babe), (0, 0xdead_beef * 0xcafe_babe));
+    }
+    #[test]
+    fn bounded_is_unbiased() {
+        // every accepted value is hit exactly as often as every other for all possible 8 bit inputs
+        for range in 1..=256 {
+            let mut counts = [0_u32; 256];
+            for number in 0..256 {
+                if let Some(value) = bounded(number, range, 8) {counts[usize::try_from(value).unwrap()] += 1;}
+            }
+            let expected = 256 / u32::try_from(range).unwrap();
+            assert!(counts[..usize::try_from(range).unwrap()].iter().all(|count| *count == expected));
+            assert!(counts[usize::try_from(range).unwrap()..].iter().all(|count| *count == 0));
+        }
+        assert_eq!(bounded(u128::MAX, 3, 128), Some(2));
+        assert_eq!(bounded(0, 3, 128), None); // 2^128 % 3 == 1 so the lowest number is rejected
+        assert_eq!(bounded(1, 3, 128), Some(0));
+    }
+    #[test]
+    fn multiplied_ranges() {
+        let mut prng = AcornBuilder::new().version(Version::V3).build();
+        let mut counts = [0_u32; 6];
+        for _ in 0..6000 {counts[usize::from(prng.generate_u8_between_range(1..=6)) - 1] += 1;}
+        assert!(counts.iter().all(|count| (900..1100).contains(count)));
+        let (mut upper, mut high) = (false, false);
+        for _ in 0..1000 {
+            upper |= prng.generate_u128_between_range(0..=16) == 16;
+            high |= prng.generate_u128_between_range(0..=u128::MAX - 1) >= 2_u128.pow(127);
+        }
+        assert!(upper && high);
+        let mut prng = AcornBuilder::new().modulus(30).version(Version::V3).build();
+        assert!((0..1000).any(|_| prng.generate_u64_between_range(0..=u64::MAX) > u64::from(u32::MAX)));
+    }
+    #[test]
+    fn masked_ranges_wider_than_the_modulus() {
+        let mut prng = AcornBuilder::new().modulus(30).version(Version::V2).build();
+        let (mut digits, mut full, mut wide) = (false, false, false);
+        for _ in 0..1000 {
+            let number = prng.generate_fixed_length_u64(20);
+            assert!(number >= 10_000_000_000_000_000_000);
+            digits |= number > 10_000_000_000_000_000_000 + 2_u64.pow(30);
+            full |= prng.generate_u64_between_range(0..=u64::MAX) > u64::from(u32::MAX);
+            wide |= prng.generate_u128_between_range(0..=u128::MAX - 1) >= 2_u128.pow(127);
+        }
+        assert!(digits && full && wide);
+        let mut prng = Acorn::with_modulus(Order::new(45), Seed::new(1_000_000), Modulus::new(30));
+        assert!((0..1000).all(|_| prng.generate_u64_between_range(0..=u64::MAX) < 2_u64.pow(30))); // the original
+        let mut prng = Acorn::with_version(Order::new(45), Seed::new(1_000_000), Version::V2);
+        let mut high = 0;
+        for _ in 0..1000 {high += u32::from(prng.generate_fixed_length_u128(39) >= 2 * 10_u128.pow(38));}
+        assert!((500..670).contains(&high)); // about 58% of 39 digit numbers are at least 2 * 10^38
+    }
+    fn period(bits: &[bool]) -> Option<usize> {
+        (1..=bits.len() / 4).find(|period| bits.iter().zip(&bits[*period..]).all(|(a, b)| a == b))
+    }
+    #[test]
+    fn low_bit_periods() {
+        let mut raw = AcornBuilder::new().version(Version::V3).build();
+        let mut tempered = AcornBuilder::new().version(Version::V4).build();
+        let mut high = AcornBuilder::new().version(Version::V3).build();
+        let (mut raw_bits, mut tempered_bits, mut high_bits) = ([false; 4096], [false; 4096], [false; 4096]);
+        for index in 0..4096 {
+            let raw = raw.generate_u128();
+            raw_bits[index] = raw & 1 == 1;
+            tempered_bits[index] = tempered.generate_u128() & 1 == 1;
+            high_bits[index] = high.generate_bits(1) == 1;
+            assert_eq!(high_bits[index], raw >> 119 == 1);
+        }
+        assert_eq!(period(&raw_bits), Some(2));
+        assert_eq!(period(&tempered_bits), None);
+        assert_eq!(period(&high_bits), None);
+        let mut eighth = [false; 4096];
+        let mut raw = AcornBuilder::new().version(Version::V3).build();
+        for bit in &mut eighth {*bit = (raw.generate_u128() >> 7) & 1 == 1;}
+        assert_eq!(period(&eighth), Some(256));
+    }
+    #[test]
+    fn temper_is_bijective() {
+        let mut outputs: std::vec::Vec<u128> = (0..1 << 16).map(|number| temper(number, 30)).collect();
+        assert!(outputs.iter().all(|number| *number < 1 << 30));
+        outputs.sort_unstable();
+        outputs.dedup();
+        assert_eq!(outputs.len(), 1 << 16);
+        assert_ne!(temper(1, 120), 1);
+    }
+    #[test]
     fn new_u128() {
         let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
         assert_eq!(prng.generate_u128(), 412_619_346_714_740_768_478_515_842_161_398_482);
@@ -539,6 +1795,66 @@ mod tests {
         assert_eq!(prng.generate_fixed_length_number(3, &NumType::U128), 822);
     }
     #[test]
+    fn new_fixed_bit_length() {
+        let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+        for bits in 1..=8 {
+            assert_eq!(u32::from(prng.generate_fixed_bit_length_u8(bits)).ilog2(), bits - 1);
+            assert_eq!(prng.generate_fixed_bit_length_u64(bits * 8).ilog2(), bits * 8 - 1);
+            assert_eq!(prng.generate_fixed_bit_length_i32(bits * 4).unsigned_abs().ilog2(), bits * 4 - 1);
+        }
+        assert_eq!(prng.generate_fixed_bit_length_u16(0), 1);
+        assert_eq!(prng.generate_fixed_bit_length_u32(100).ilog2(), 31);
+        assert_eq!(prng.generate_fixed_bit_length_u128(200).ilog2(), 127);
+        assert_eq!(prng.generate_fixed_bit_length_usize(usize::BITS).ilog2(), usize::BITS - 1);
+        assert_eq!(prng.generate_fixed_bit_length_i8(8), i8::MIN);
+        assert_eq!(prng.generate_fixed_bit_length_i128(128), i128::MIN);
+        assert_eq!(prng.generate_fixed_bit_length_i16(0).unsigned_abs(), 1);
+        assert_eq!(prng.generate_fixed_bit_length_isize(3).unsigned_abs().ilog2(), 2);
+        let (mut negative, mut positive) = (false, false);
+        for _ in 0..100 {
+            let number = prng.generate_fixed_bit_length_i64(63);
+            assert_eq!(number.unsigned_abs().ilog2(), 62);
+            negative |= number < 0;
+            positive |= number > 0;
+        }
+        assert!(negative && positive);
+    }
+    #[test]
+    fn try_fixed_bit_length() {
+        let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+        assert_eq!(prng.try_generate_fixed_bit_length_u8(0), None);
+        assert_eq!(prng.try_generate_fixed_bit_length_u8(9), None);
+        assert_eq!(prng.try_generate_fixed_bit_length_i8(9), None);
+        assert_eq!(prng.try_generate_fixed_bit_length_u128(129), None);
+        assert_eq!(prng.position(), 0); // rejected inputs draw nothing
+        let mut expected = Acorn::new(Order::new(45), Seed::new(1_000_000));
+        assert_eq!(prng.try_generate_fixed_bit_length_i8(8), Some(expected.generate_fixed_bit_length_i8(8)));
+        assert_eq!(prng.try_generate_fixed_bit_length_u32(20), Some(expected.generate_fixed_bit_length_u32(20)));
+        assert_eq!(prng.try_generate_fixed_bit_length_i64(64), Some(expected.generate_fixed_bit_length_i64(64)));
+    }
+    #[test]
+    fn fixed_bit_length_above_the_modulus() {
+        for modulus in [120, 30] {
+            let mut prng = AcornBuilder::new().modulus(modulus).version(Version::V2).build();
+            let mut quarters = [0_u32; 2];
+            let mut low = 0;
+            for _ in 0..1000 {
+                let number = prng.generate_fixed_bit_length_u128(128);
+                assert_eq!(number.ilog2(), 127);
+                quarters[usize::from(number >= 3 << 126)] += 1;
+                if number & ((1 << 64) - 1) >= 1 << 63 {low += 1;}
+            }
+            assert!(quarters.iter().all(|count| (430..570).contains(count)), "{quarters:?}");
+            assert!((430..570).contains(&low), "{low}");
+        }
+    }
+    #[test]
+    fn bit_bounds_testing() {
+        assert_eq!(Acorn::generate_bit_bounds(1), (1, 1));
+        assert_eq!(Acorn::generate_bit_bounds(8), (128, 255));
+        assert_eq!(Acorn::generate_bit_bounds(128), (1 << 127, u128::MAX));
+    }
+    #[test]
     fn new_usize_between_range_different_length() {
         let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
         assert_eq!(prng.generate_usize_between_range(71..=777), 571);
@@ -0,0 +1,1146 @@
+use core::ops::RangeInclusive;
+
+use crate::{Acorn, AcornUniform, Ulid, Uuid};
+
+/// The integer type of a recorded [Call](enum.Call.html).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum IntType {
+    /// [`u8`](https://doc.rust-lang.org/core/primitive.u8.html)
+    U8,
+    /// [`u16`](https://doc.rust-lang.org/core/primitive.u16.html)
+    U16,
+    /// [`u32`](https://doc.rust-lang.org/core/primitive.u32.html)
+    U32,
+    /// [`u64`](https://doc.rust-lang.org/core/primitive.u64.html)
+    U64,
+    /// [`u128`](https://doc.rust-lang.org/core/primitive.u128.html)
+    U128,
+    /// [`usize`](https://doc.rust-lang.org/core/primitive.usize.html)
+    Usize,
+}
+
+impl IntType {
+    const fn from_index(index: u8) -> Option<Self> {
+        match index {
+            0 => Some(IntType::U8),
+            1 => Some(IntType::U16),
+            2 => Some(IntType::U32),
+            3 => Some(IntType::U64),
+            4 => Some(IntType::U128),
+            5 => Some(IntType::Usize),
+            _ => None,
+        }
+    }
+    const fn index(self) -> u8 {
+        match self {
+            IntType::U8 => 0,
+            IntType::U16 => 1,
+            IntType::U32 => 2,
+            IntType::U64 => 3,
+            IntType::U128 => 4,
+            IntType::Usize => 5,
+        }
+    }
+}
+
+/// The type of a value recorded by a generic [Call](enum.Call.html), see [Recordable](trait.Recordable.html).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ValueType {
+    /// [`u8`](https://doc.rust-lang.org/core/primitive.u8.html)
+    U8,
+    /// [`u16`](https://doc.rust-lang.org/core/primitive.u16.html)
+    U16,
+    /// [`u32`](https://doc.rust-lang.org/core/primitive.u32.html)
+    U32,
+    /// [`u64`](https://doc.rust-lang.org/core/primitive.u64.html)
+    U64,
+    /// [`u128`](https://doc.rust-lang.org/core/primitive.u128.html)
+    U128,
+    /// [`usize`](https://doc.rust-lang.org/core/primitive.usize.html)
+    Usize,
+    /// [`i8`](https://doc.rust-lang.org/core/primitive.i8.html)
+    I8,
+    /// [`i16`](https://doc.rust-lang.org/core/primitive.i16.html)
+    I16,
+    /// [`i32`](https://doc.rust-lang.org/core/primitive.i32.html)
+    I32,
+    /// [`i64`](https://doc.rust-lang.org/core/primitive.i64.html)
+    I64,
+    /// [`i128`](https://doc.rust-lang.org/core/primitive.i128.html)
+    I128,
+    /// [`isize`](https://doc.rust-lang.org/core/primitive.isize.html)
+    Isize,
+    /// [`f32`](https://doc.rust-lang.org/core/primitive.f32.html)
+    F32,
+    /// [`f64`](https://doc.rust-lang.org/core/primitive.f64.html)
+    F64,
+    /// [`bool`](https://doc.rust-lang.org/core/primitive.bool.html)
+    Bool,
+    /// [`char`](https://doc.rust-lang.org/core/primitive.char.html)
+    Char,
+}
+
+impl ValueType {
+    const ALL: [Self; 16] = [
+        ValueType::U8, ValueType::U16, ValueType::U32, ValueType::U64, ValueType::U128, ValueType::Usize,
+        ValueType::I8, ValueType::I16, ValueType::I32, ValueType::I64, ValueType::I128, ValueType::Isize,
+        ValueType::F32, ValueType::F64, ValueType::Bool, ValueType::Char,
+    ];
+    const fn from_index(index: u8) -> Option<Self> {
+        if (index as usize) < Self::ALL.len() {Some(Self::ALL[index as usize])} else {None}
+    }
+    const fn index(self) -> u8 {
+        self as u8
+    }
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// Types whose generic draws can be recorded by a [Recording](struct.Recording.html) and replayed by a
+/// [Replay](struct.Replay.html).
+///
+/// This is implemented for every primitive type that implements [`AcornUniform`](trait.AcornUniform.html) and cannot be
+/// implemented outside this crate, because a log must name the type of every value it holds. Values are recorded
+/// losslessly: signed integers are sign-extended, floating point numbers are stored as their bits and characters
+/// as their code points.
+pub trait Recordable: AcornUniform + Copy + sealed::Sealed {
+    /// The type named in the log.
+    const VALUE_TYPE: ValueType;
+    /// Convert the value to a recorded result.
+    fn to_recorded(self) -> u128;
+    /// Convert a recorded result back to the value.
+    fn from_recorded(recorded: u128) -> Self;
+}
+
+macro_rules! impl_recordable {
+    ($($t:ty => $value_type:ident, |$value:ident| $to:expr, |$recorded:ident| $from:expr;)*) => {$(
+        impl sealed::Sealed for $t {}
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_possible_wrap, clippy::cast_lossless)]
+        impl Recordable for $t {
+            const VALUE_TYPE: ValueType = ValueType::$value_type;
+            fn to_recorded(self) -> u128 {
+                let $value = self;
+                $to
+            }
+            fn from_recorded(recorded: u128) -> Self {
+                let $recorded = recorded;
+                $from
+            }
+        }
+    )*};
+}
+
+impl_recordable! {
+    u8 => U8, |value| value as u128, |recorded| recorded as u8;
+    u16 => U16, |value| value as u128, |recorded| recorded as u16;
+    u32 => U32, |value| value as u128, |recorded| recorded as u32;
+    u64 => U64, |value| value as u128, |recorded| recorded as u64;
+    u128 => U128, |value| value, |recorded| recorded;
+    usize => Usize, |value| value as u128, |recorded| recorded as usize;
+    i8 => I8, |value| value as u128, |recorded| recorded as i8;
+    i16 => I16, |value| value as u128, |recorded| recorded as i16;
+    i32 => I32, |value| value as u128, |recorded| recorded as i32;
+    i64 => I64, |value| value as u128, |recorded| recorded as i64;
+    i128 => I128, |value| value as u128, |recorded| recorded as i128;
+    isize => Isize, |value| value as u128, |recorded| recorded as isize;
+    f32 => F32, |value| value.to_bits() as u128, |recorded| f32::from_bits(recorded as u32);
+    f64 => F64, |value| value.to_bits() as u128, |recorded| f64::from_bits(recorded as u64);
+    bool => Bool, |value| value as u128, |recorded| recorded != 0;
+    char => Char, |value| value as u128, |recorded| {
+        char::from_u32(recorded as u32).unwrap_or(char::REPLACEMENT_CHARACTER) // recorded from a valid char
+    };
+}
+
+/// A generation method of an [Acorn](struct.Acorn.html) generator together with its arguments.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Call {
+    /// [`generate_u128`](struct.Acorn.html#method.generate_u128)
+    Raw,
+    /// [`generate_bits`](struct.Acorn.html#method.generate_bits) with the number of bits.
+    Bits(u32),
+    /// `generate_*_between_range` with the start and end of the range.
+    Range(IntType, u128, u128),
+    /// `generate_fixed_length_*` with the number of digits.
+    FixedLength(IntType, usize),
+    /// `generate_fixed_bit_length_*` for unsigned types with the number of bits.
+    FixedBitLength(IntType, u32),
+    /// `generate_fixed_bit_length_*` for signed types, named by the unsigned type of the same width, with the number
+    /// of bits. The result is sign-extended to 128 bits.
+    SignedFixedBitLength(IntType, u32),
+    /// [`generate`](struct.Acorn.html#method.generate) of a [Recordable](trait.Recordable.html) type.
+    Value(ValueType),
+    /// [`generate_range`](struct.Acorn.html#method.generate_range) of a [Recordable](trait.Recordable.html) type with
+    /// the recorded start and end of the range.
+    ValueRange(ValueType, u128, u128),
+    /// [`generate_uuid_v4`](struct.Acorn.html#method.generate_uuid_v4)
+    UuidV4,
+    /// [`generate_uuid_v7`](struct.Acorn.html#method.generate_uuid_v7) with the timestamp.
+    UuidV7(u64),
+    /// [`generate_ulid`](struct.Acorn.html#method.generate_ulid) with the timestamp.
+    Ulid(u64),
+}
+
+/// A single recorded generation call: the [Call](enum.Call.html), its result and the number of steps it took.
+///
+/// A draw is encoded as one tag byte followed by the arguments, the result and the steps as unsigned LEB128
+/// numbers, so small values take a single byte each.
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::{Call, Draw, IntType};
+///
+/// let draw = Draw {call: Call::Range(IntType::U8, 1, 6), result: 4, steps: 1};
+/// let mut buffer = [0; Draw::MAX_BYTES];
+/// let length = draw.encode(&mut buffer).unwrap();
+///
+/// assert_eq!(length, 5);
+/// assert_eq!(Draw::decode(&buffer[..length]), Some((draw, length)));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Draw {
+    /// The method and its arguments.
+    pub call: Call,
+    /// The result, converted to a [`u128`](https://doc.rust-lang.org/core/primitive.u128.html).
+    pub result: u128,
+    /// The number of [steps](struct.Acorn.html#method.position) the call took.
+    pub steps: u128,
+}
+
+const fn encode_number(buffer: &mut [u8], mut offset: usize, mut number: u128) -> Option<usize> {
+    loop {
+        if offset >= buffer.len() {return None;}
+        #[allow(clippy::cast_possible_truncation)]
+        let byte = (number & 0x7F) as u8;
+        number >>= 7;
+        if number == 0 {
+            buffer[offset] = byte;
+            return Some(offset + 1);
+        }
+        buffer[offset] = byte | 0x80;
+        offset += 1;
+    }
+}
+
+const fn decode_number(bytes: &[u8], mut offset: usize) -> Option<(u128, usize)> {
+    let mut number = 0;
+    let mut shift = 0;
+    loop {
+        if offset >= bytes.len() || shift > 126 {return None;}
+        let byte = bytes[offset];
+        if shift == 126 && byte > 0x03 {return None;} // more than 128 bits
+        number |= ((byte & 0x7F) as u128) << shift;
+        offset += 1;
+        if byte & 0x80 == 0 {return Some((number, offset));}
+        shift += 7;
+    }
+}
+
+impl Draw {
+    /// The largest number of bytes a single encoded draw can take.
+    pub const MAX_BYTES: usize = 1 + 4 * 19;
+    /// Encode this draw at the start of a buffer and return the number of bytes written.
+    ///
+    /// Returns [`None`] if the buffer is too small, in which case its contents are unspecified.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    #[must_use]
+    pub const fn encode(&self, buffer: &mut [u8]) -> Option<usize> {
+        if buffer.is_empty() {return None;}
+        let (tag, first, second) = match self.call {
+            Call::Raw => (0, None, None),
+            Call::Bits(bits) => (1, Some(bits as u128), None),
+            Call::Range(int_type, start, end) => (2 + int_type.index(), Some(start), Some(end)),
+            Call::FixedLength(int_type, length) => (8 + int_type.index(), Some(length as u128), None),
+            Call::FixedBitLength(int_type, bits) => (14 + int_type.index(), Some(bits as u128), None),
+            Call::SignedFixedBitLength(int_type, bits) => (20 + int_type.index(), Some(bits as u128), None),
+            Call::Value(value_type) => (26 + value_type.index(), None, None),
+            Call::ValueRange(value_type, start, end) => (42 + value_type.index(), Some(start), Some(end)),
+            Call::UuidV4 => (58, None, None),
+            Call::UuidV7(unix_millis) => (59, Some(unix_millis as u128), None),
+            Call::Ulid(unix_millis) => (60, Some(unix_millis as u128), None),
+        };
+        buffer[0] = tag;
+        let mut offset = 1;
+        if let Some(first) = first {
+            let Some(next) = encode_number(buffer, offset, first) else {return None};
+            offset = next;
+        }
+        if let Some(second) = second {
+            let Some(next) = encode_number(buffer, offset, second) else {return None};
+            offset = next;
+        }
+        let Some(offset) = encode_number(buffer, offset, self.result) else {return None};
+        encode_number(buffer, offset, self.steps)
+    }
+    /// Decode a draw from the start of a log and return it with the number of bytes it took.
+    ///
+    /// Returns [`None`] if the log is empty, truncated or not a valid encoding.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn decode(bytes: &[u8]) -> Option<(Self, usize)> {
+        if bytes.is_empty() {return None;}
+        let tag = bytes[0];
+        let arguments = match tag {
+            0 | 26..=41 | 58 => 0,
+            2..=7 | 42..=57 => 2,
+            1 | 8..=25 | 59 | 60 => 1,
+            _ => return None,
+        };
+        let mut offset = 1;
+        let mut values = [0; 2];
+        let mut index = 0;
+        while index < arguments {
+            let Some((value, next)) = decode_number(bytes, offset) else {return None};
+            values[index] = value;
+            offset = next;
+            index += 1;
+        }
+        let Some(int_type) = IntType::from_index((tag + 4) % 6) else {unreachable!()}; // 2, 8, 14 and 20 are U8
+        let Some(value_type) = ValueType::from_index((tag + 6) % 16) else {unreachable!()}; // 26 and 42 are U8
+        let call = match tag {
+            0 => Call::Raw,
+            1 if values[0] <= u32::MAX as u128 => Call::Bits(values[0] as u32),
+            2..=7 => Call::Range(int_type, values[0], values[1]),
+            8..=13 if values[0] <= usize::MAX as u128 => Call::FixedLength(int_type, values[0] as usize),
+            14..=19 if values[0] <= u32::MAX as u128 => Call::FixedBitLength(int_type, values[0] as u32),
+            20..=25 if values[0] <= u32::MAX as u128 => Call::SignedFixedBitLength(int_type, values[0] as u32),
+            26..=41 => Call::Value(value_type),
+            42..=57 => Call::ValueRange(value_type, values[0], values[1]),
+            58 => Call::UuidV4,
+            59 if values[0] <= u64::MAX as u128 => Call::UuidV7(values[0] as u64),
+            60 if values[0] <= u64::MAX as u128 => Call::Ulid(values[0] as u64),
+            _ => return None,
+        };
+        let Some((result, offset)) = decode_number(bytes, offset) else {return None};
+        let Some((steps, offset)) = decode_number(bytes, offset) else {return None};
+        Some((Draw {call, result, steps}, offset))
+    }
+}
+
+/// Storage for the encoded draws of a [Recording](struct.Recording.html).
+pub trait DrawLog {
+    /// Append an encoded draw, returning whether it was stored completely.
+    fn append(&mut self, draw: &[u8]) -> bool;
+}
+
+/// A [`DrawLog`](trait.DrawLog.html) in a caller-provided buffer for use without allocation.
+///
+/// Once a draw does not fit, it and every later draw are dropped so the log always holds a complete prefix.
+#[derive(Debug)]
+pub struct SliceLog<'a> {
+    buffer: &'a mut [u8],
+    length: usize,
+    full: bool,
+}
+
+impl<'a> SliceLog<'a> {
+    /// Create an empty log writing into `buffer`.
+    #[must_use]
+    pub const fn new(buffer: &'a mut [u8]) -> Self {
+        Self {buffer, length: 0, full: false}
+    }
+    /// The encoded draws recorded so far.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &[u8] {
+        self.buffer.split_at(self.length).0
+    }
+    /// Whether a draw has been dropped because the buffer was full.
+    #[must_use]
+    pub const fn is_full(&self) -> bool {
+        self.full
+    }
+}
+
+impl DrawLog for SliceLog<'_> {
+    fn append(&mut self, draw: &[u8]) -> bool {
+        if self.full || self.buffer.len() - self.length < draw.len() {
+            self.full = true;
+            return false;
+        }
+        self.buffer[self.length..self.length + draw.len()].copy_from_slice(draw);
+        self.length += draw.len();
+        true
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl DrawLog for alloc::vec::Vec<u8> {
+    fn append(&mut self, draw: &[u8]) -> bool {
+        self.extend_from_slice(draw);
+        true
+    }
+}
+
+/// A [`DrawLog`](trait.DrawLog.html) writing to any [`Write`], such as a [`File`], which requires the `std` feature.
+///
+/// The first write error is kept and every later draw is dropped.
+///
+/// [`Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+/// [`File`]: https://doc.rust-lang.org/std/fs/struct.File.html
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct IoLog<W: std::io::Write> {
+    writer: W,
+    error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> IoLog<W> {
+    /// Create a log writing to `writer`.
+    pub const fn new(writer: W) -> Self {
+        Self {writer, error: None}
+    }
+    /// Flush and return the writer.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error that occurred while writing or flushing.
+    pub fn into_inner(mut self) -> std::io::Result<W> {
+        match self.error {
+            Some(error) => Err(error),
+            None => self.writer.flush().map(|()| self.writer),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<W: std::io::Write> DrawLog for IoLog<W> {
+    fn append(&mut self, draw: &[u8]) -> bool {
+        if self.error.is_some() {return false;}
+        match self.writer.write_all(draw) {
+            Ok(()) => true,
+            Err(error) => {
+                self.error = Some(error);
+                false
+            },
+        }
+    }
+}
+
+/// An [Acorn](struct.Acorn.html) generator that records every draw into a [`DrawLog`](trait.DrawLog.html).
+///
+/// The log can be attached to a bug report and fed back with [Replay](struct.Replay.html) to find the first draw
+/// where another run diverges. To compare the results and steps as well as the calls, record the other run into the
+/// replay itself, since [Replay](struct.Replay.html) is a [`DrawLog`](trait.DrawLog.html) that checks every draw.
+///
+/// Every fixed-size draw has a [Call](enum.Call.html): raw outputs, bits, typed ranges, fixed digit and bit lengths,
+/// generic values and ranges of every [Recordable](trait.Recordable.html) type, and UUIDs and ULIDs. Draws of a
+/// variable number of bytes, that is digits, strings and nanoids, are not recorded, so a recording does not offer
+/// them at all rather than leave a gap in the log:
+///
+/// ```compile_fail
+/// use acorn_prng::{Acorn, Order, Recording, Seed, SliceLog};
+///
+/// let mut buffer = [0; 256];
+/// let mut recording = Recording::new(Acorn::new(Order::new(45), Seed::new(1_000_000)), SliceLog::new(&mut buffer));
+/// let id = recording.generate_nanoid::<21>(); // not recordable
+/// ```
+///
+/// Build them from the recorded methods or take the generator back with [`into_parts`] first.
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::{Acorn, Order, Recording, Replay, Seed, SliceLog};
+///
+/// let mut buffer = [0; 256];
+/// let mut recording = Recording::new(Acorn::new(Order::new(45), Seed::new(1_000_000)), SliceLog::new(&mut buffer));
+/// let die = recording.generate_u8_between_range(1..=6);
+/// let number = recording.generate_fixed_length_u32(3);
+/// let (_, log) = recording.into_parts();
+///
+/// let mut replay = Replay::new(log.as_bytes());
+/// assert_eq!(replay.generate_u8_between_range(1..=6), Ok(die));
+/// assert!(replay.generate_fixed_length_u32(4).is_err()); // asks for a different length
+/// ```
+///
+/// [`into_parts`]: struct.Recording.html#method.into_parts
+#[derive(Debug)]
+pub struct Recording<L: DrawLog> {
+    prng: Acorn,
+    log: L,
+    complete: bool,
+}
+
+impl<L: DrawLog> Recording<L> {
+    /// Start recording the draws of a generator.
+    pub const fn new(prng: Acorn, log: L) -> Self {
+        Self {prng, log, complete: true}
+    }
+    /// Stop recording and return the generator and the log.
+    pub fn into_parts(self) -> (Acorn, L) {
+        (self.prng, self.log)
+    }
+    /// Whether every draw so far has been stored in the log.
+    pub const fn is_complete(&self) -> bool {
+        self.complete
+    }
+    fn record(&mut self, call: Call, result: u128, position: u128) -> u128 {
+        let draw = Draw {call, result, steps: self.prng.position().wrapping_sub(position)};
+        let mut buffer = [0; Draw::MAX_BYTES];
+        let length = draw.encode(&mut buffer).unwrap_or_default();
+        self.complete &= self.log.append(&buffer[..length]);
+        result
+    }
+    /// Generate and record a raw [`u128`], see [`Acorn::generate_u128`].
+    ///
+    /// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+    /// [`Acorn::generate_u128`]: struct.Acorn.html#method.generate_u128
+    pub fn generate_u128(&mut self) -> u128 {
+        let position = self.prng.position();
+        let result = self.prng.generate_u128();
+        self.record(Call::Raw, result, position)
+    }
+    /// Generate and record a number of up to 128 `bits`, see [`Acorn::generate_bits`].
+    ///
+    /// [`Acorn::generate_bits`]: struct.Acorn.html#method.generate_bits
+    pub fn generate_bits(&mut self, bits: u32) -> u128 {
+        let position = self.prng.position();
+        let result = self.prng.generate_bits(bits);
+        self.record(Call::Bits(bits), result, position)
+    }
+    /// Generate and record a value of a [Recordable](trait.Recordable.html) type, see [`Acorn::generate`].
+    ///
+    /// [`Acorn::generate`]: struct.Acorn.html#method.generate
+    pub fn generate<T: Recordable>(&mut self) -> T {
+        let position = self.prng.position();
+        let result: T = self.prng.generate();
+        self.record(Call::Value(T::VALUE_TYPE), result.to_recorded(), position);
+        result
+    }
+    /// Generate and record a value of a [Recordable](trait.Recordable.html) type within a given [`RangeInclusive`],
+    /// see [`Acorn::generate_range`].
+    ///
+    /// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+    /// [`Acorn::generate_range`]: struct.Acorn.html#method.generate_range
+    pub fn generate_range<T: Recordable>(&mut self, range: RangeInclusive<T>) -> T {
+        let position = self.prng.position();
+        let call = Call::ValueRange(T::VALUE_TYPE, range.start().to_recorded(), range.end().to_recorded());
+        let result = self.prng.generate_range(range);
+        self.record(call, result.to_recorded(), position);
+        result
+    }
+    /// Generate and record a version 4 [Uuid](struct.Uuid.html), see [`Acorn::generate_uuid_v4`].
+    ///
+    /// [`Acorn::generate_uuid_v4`]: struct.Acorn.html#method.generate_uuid_v4
+    pub fn generate_uuid_v4(&mut self) -> Uuid {
+        let position = self.prng.position();
+        let result = self.prng.generate_uuid_v4();
+        self.record(Call::UuidV4, u128::from_be_bytes(result.to_bytes()), position);
+        result
+    }
+    /// Generate and record a version 7 [Uuid](struct.Uuid.html), see [`Acorn::generate_uuid_v7`].
+    ///
+    /// [`Acorn::generate_uuid_v7`]: struct.Acorn.html#method.generate_uuid_v7
+    pub fn generate_uuid_v7(&mut self, unix_millis: u64) -> Uuid {
+        let position = self.prng.position();
+        let result = self.prng.generate_uuid_v7(unix_millis);
+        self.record(Call::UuidV7(unix_millis), u128::from_be_bytes(result.to_bytes()), position);
+        result
+    }
+    /// Generate and record a [Ulid](struct.Ulid.html), see [`Acorn::generate_ulid`].
+    ///
+    /// [`Acorn::generate_ulid`]: struct.Acorn.html#method.generate_ulid
+    pub fn generate_ulid(&mut self, unix_millis: u64) -> Ulid {
+        let position = self.prng.position();
+        let result = self.prng.generate_ulid(unix_millis);
+        self.record(Call::Ulid(unix_millis), u128::from_be_bytes(result.to_bytes()), position);
+        result
+    }
+}
+
+/// What differed at a [Divergence](struct.Divergence.html).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mismatch {
+    /// The run asked for a different call, or the log ended or could not be decoded.
+    Call,
+    /// The call matched but the generator returned a different result.
+    Result,
+    /// The call and result matched but the generator took a different number of steps.
+    Steps,
+}
+
+/// The first draw where a run asked for something other than what was recorded.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Divergence {
+    /// The number of draws that matched before this one.
+    pub index: usize,
+    /// The recorded call, or [`None`] if the log ended or could not be decoded.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    pub expected: Option<Call>,
+    /// What the run asked for instead.
+    pub actual: Call,
+    /// What differed, which is only ever the result or the steps when the run was recorded into the
+    /// [Replay](struct.Replay.html).
+    pub mismatch: Mismatch,
+}
+
+/// Feeds the draws of a [Recording](struct.Recording.html) back without a generator.
+///
+/// Every call returns the recorded result as long as the run asks for exactly the recorded [Call](enum.Call.html).
+/// The first mismatch is returned as a [Divergence](struct.Divergence.html), as is every call after it, and
+/// [`position`] follows the recorded steps.
+///
+/// A replay is also a [`DrawLog`](trait.DrawLog.html): a [Recording](struct.Recording.html) into it checks the
+/// result and the number of steps of every draw against the log as well, and stores nothing once they diverge.
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::{Acorn, Mismatch, Order, Recording, Replay, Seed, SliceLog};
+///
+/// let mut buffer = [0; 64];
+/// let mut recording = Recording::new(Acorn::new(Order::new(45), Seed::new(1_000_000)), SliceLog::new(&mut buffer));
+/// recording.generate_range(-1.0..=1.0);
+/// let (_, log) = recording.into_parts();
+///
+/// let mut check = Recording::new(Acorn::new(Order::new(45), Seed::new(2_000_000)), Replay::new(log.as_bytes()));
+/// check.generate_range(-1.0..=1.0);
+/// let (_, replay) = check.into_parts();
+///
+/// assert_eq!(replay.divergence().unwrap().mismatch, Mismatch::Result);
+/// ```
+///
+/// [`position`]: struct.Replay.html#method.position
+#[derive(Debug)]
+pub struct Replay<'a> {
+    log: &'a [u8],
+    offset: usize,
+    index: usize,
+    position: u128,
+    divergence: Option<Divergence>,
+}
+
+impl<'a> Replay<'a> {
+    /// Replay an encoded log.
+    #[must_use]
+    pub const fn new(log: &'a [u8]) -> Self {
+        Self {log, offset: 0, index: 0, position: 0, divergence: None}
+    }
+    /// The number of draws replayed so far.
+    #[must_use]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+    /// The number of steps the replayed draws took, which is how far the recorded generator had
+    /// [advanced](struct.Acorn.html#method.position) from where the recording started.
+    #[must_use]
+    pub const fn position(&self) -> u128 {
+        self.position
+    }
+    /// Whether every recorded draw has been replayed.
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        self.offset == self.log.len()
+    }
+    /// The first divergence, if any.
+    #[must_use]
+    pub const fn divergence(&self) -> Option<Divergence> {
+        self.divergence
+    }
+    /// Replay the next draw if it matches `call`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [Divergence](struct.Divergence.html) if this or any earlier call did not match.
+    pub const fn next_draw(&mut self, call: Call) -> Result<u128, Divergence> {
+        self.advance(call, None)
+    }
+    /// Replay the next draw if it matches the `call`, `result` and `steps` of a draw made by a generator.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [Divergence](struct.Divergence.html) if this or any earlier draw did not match.
+    pub const fn check(&mut self, draw: Draw) -> Result<(), Divergence> {
+        match self.advance(draw.call, Some((draw.result, draw.steps))) {
+            Ok(_) => Ok(()),
+            Err(divergence) => Err(divergence),
+        }
+    }
+    const fn advance(&mut self, call: Call, generated: Option<(u128, u128)>) -> Result<u128, Divergence> {
+        if let Some(divergence) = self.divergence {return Err(divergence);}
+        let (_, remaining) = self.log.split_at(self.offset);
+        let (expected, mismatch) = match Draw::decode(remaining) {
+            Some((draw, length)) => {
+                let mismatch = match generated {
+                    _ if !draw.call.matches(call) => Some(Mismatch::Call),
+                    Some((result, _)) if result != draw.result => Some(Mismatch::Result),
+                    Some((_, steps)) if steps != draw.steps => Some(Mismatch::Steps),
+                    _ => None,
+                };
+                let Some(mismatch) = mismatch else {
+                    self.offset += length;
+                    self.index += 1;
+                    self.position = self.position.wrapping_add(draw.steps);
+                    return Ok(draw.result);
+                };
+                (Some(draw.call), mismatch)
+            },
+            None => (None, Mismatch::Call),
+        };
+        let divergence = Divergence {index: self.index, expected, actual: call, mismatch};
+        self.divergence = Some(divergence);
+        Err(divergence)
+    }
+    /// Replay a raw [`u128`], see [`Acorn::generate_u128`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [Divergence](struct.Divergence.html) if this or any earlier call did not match.
+    ///
+    /// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+    /// [`Acorn::generate_u128`]: struct.Acorn.html#method.generate_u128
+    pub const fn generate_u128(&mut self) -> Result<u128, Divergence> {
+        self.next_draw(Call::Raw)
+    }
+    /// Replay a number of up to 128 `bits`, see [`Acorn::generate_bits`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [Divergence](struct.Divergence.html) if this or any earlier call did not match.
+    ///
+    /// [`Acorn::generate_bits`]: struct.Acorn.html#method.generate_bits
+    pub const fn generate_bits(&mut self, bits: u32) -> Result<u128, Divergence> {
+        self.next_draw(Call::Bits(bits))
+    }
+    /// Replay a value of a [Recordable](trait.Recordable.html) type, see [`Acorn::generate`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [Divergence](struct.Divergence.html) if this or any earlier call did not match.
+    ///
+    /// [`Acorn::generate`]: struct.Acorn.html#method.generate
+    pub fn generate<T: Recordable>(&mut self) -> Result<T, Divergence> {
+        self.next_draw(Call::Value(T::VALUE_TYPE)).map(T::from_recorded)
+    }
+    /// Replay a value of a [Recordable](trait.Recordable.html) type within a given [`RangeInclusive`], see
+    /// [`Acorn::generate_range`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [Divergence](struct.Divergence.html) if this or any earlier call did not match.
+    ///
+    /// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+    /// [`Acorn::generate_range`]: struct.Acorn.html#method.generate_range
+    pub fn generate_range<T: Recordable>(&mut self, range: RangeInclusive<T>) -> Result<T, Divergence> {
+        let call = Call::ValueRange(T::VALUE_TYPE, range.start().to_recorded(), range.end().to_recorded());
+        self.next_draw(call).map(T::from_recorded)
+    }
+    /// Replay a version 4 [Uuid](struct.Uuid.html), see [`Acorn::generate_uuid_v4`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [Divergence](struct.Divergence.html) if this or any earlier call did not match.
+    ///
+    /// [`Acorn::generate_uuid_v4`]: struct.Acorn.html#method.generate_uuid_v4
+    pub const fn generate_uuid_v4(&mut self) -> Result<Uuid, Divergence> {
+        match self.next_draw(Call::UuidV4) {
+            Ok(result) => Ok(Uuid::from_bytes(result.to_be_bytes())),
+            Err(divergence) => Err(divergence),
+        }
+    }
+    /// Replay a version 7 [Uuid](struct.Uuid.html), see [`Acorn::generate_uuid_v7`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [Divergence](struct.Divergence.html) if this or any earlier call did not match.
+    ///
+    /// [`Acorn::generate_uuid_v7`]: struct.Acorn.html#method.generate_uuid_v7
+    pub const fn generate_uuid_v7(&mut self, unix_millis: u64) -> Result<Uuid, Divergence> {
+        match self.next_draw(Call::UuidV7(unix_millis)) {
+            Ok(result) => Ok(Uuid::from_bytes(result.to_be_bytes())),
+            Err(divergence) => Err(divergence),
+        }
+    }
+    /// Replay a [Ulid](struct.Ulid.html), see [`Acorn::generate_ulid`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the first [Divergence](struct.Divergence.html) if this or any earlier call did not match.
+    ///
+    /// [`Acorn::generate_ulid`]: struct.Acorn.html#method.generate_ulid
+    pub const fn generate_ulid(&mut self, unix_millis: u64) -> Result<Ulid, Divergence> {
+        match self.next_draw(Call::Ulid(unix_millis)) {
+            Ok(result) => Ok(Ulid::from_bytes(result.to_be_bytes())),
+            Err(divergence) => Err(divergence),
+        }
+    }
+}
+
+impl DrawLog for Replay<'_> {
+    /// Check an encoded draw against the log, returning whether it matched.
+    fn append(&mut self, draw: &[u8]) -> bool {
+        match Draw::decode(draw) {
+            Some((draw, _)) => self.check(draw).is_ok(),
+            None => false,
+        }
+    }
+}
+
+impl Call {
+    /// Compare two calls like the derived [`PartialEq`], which cannot be called from the `const fn` replay methods.
+    ///
+    /// [`PartialEq`]: https://doc.rust-lang.org/core/cmp/trait.PartialEq.html
+    const fn matches(self, other: Call) -> bool {
+        match (self, other) {
+            (Call::Raw, Call::Raw) | (Call::UuidV4, Call::UuidV4) => true,
+            (Call::Bits(first), Call::Bits(second)) => first == second,
+            (Call::Range(first_type, first_start, first_end), Call::Range(second_type, second_start, second_end)) => {
+                first_type.index() == second_type.index() && first_start == second_start && first_end == second_end
+            },
+            (Call::FixedLength(first_type, first), Call::FixedLength(second_type, second)) => {
+                first_type.index() == second_type.index() && first == second
+            },
+            (Call::FixedBitLength(first_type, first), Call::FixedBitLength(second_type, second))
+            | (Call::SignedFixedBitLength(first_type, first), Call::SignedFixedBitLength(second_type, second)) => {
+                first_type.index() == second_type.index() && first == second
+            },
+            (Call::Value(first), Call::Value(second)) => first.index() == second.index(),
+            (
+                Call::ValueRange(first_type, first_start, first_end),
+                Call::ValueRange(second_type, second_start, second_end),
+            ) => {
+                first_type.index() == second_type.index() && first_start == second_start && first_end == second_end
+            },
+            (Call::UuidV7(first), Call::UuidV7(second)) | (Call::Ulid(first), Call::Ulid(second)) => first == second,
+            _ => false,
+        }
+    }
+}
+
+macro_rules! impl_typed {
+    ($($t:ty => $int_type:ident, $between_range:ident, $fixed_length:ident, $fixed_bit_length:ident;)*) => {
+        #[allow(clippy::cast_lossless)]
+        impl<L: DrawLog> Recording<L> {$(
+            #[doc = concat!("Generate and record a random [`", stringify!($t), "`] within a given [`RangeInclusive`], see [`Acorn::", stringify!($between_range), "`].")]
+            ///
+            #[doc = concat!("[`", stringify!($t), "`]: https://doc.rust-lang.org/core/primitive.", stringify!($t), ".html")]
+            /// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+            #[doc = concat!("[`Acorn::", stringify!($between_range), "`]: struct.Acorn.html#method.", stringify!($between_range))]
+            pub fn $between_range(&mut self, range: RangeInclusive<$t>) -> $t {
+                let position = self.prng.position();
+                let call = Call::Range(IntType::$int_type, *range.start() as u128, *range.end() as u128);
+                let result = self.prng.$between_range(range);
+                self.record(call, result as u128, position);
+                result
+            }
+            #[doc = concat!("Generate and record a random [`", stringify!($t), "`] of a fixed digit length, see [`Acorn::", stringify!($fixed_length), "`].")]
+            ///
+            #[doc = concat!("[`", stringify!($t), "`]: https://doc.rust-lang.org/core/primitive.", stringify!($t), ".html")]
+            #[doc = concat!("[`Acorn::", stringify!($fixed_length), "`]: struct.Acorn.html#method.", stringify!($fixed_length))]
+            pub fn $fixed_length(&mut self, length: usize) -> $t {
+                let position = self.prng.position();
+                let result = self.prng.$fixed_length(length);
+                self.record(Call::FixedLength(IntType::$int_type, length), result as u128, position);
+                result
+            }
+            #[doc = concat!("Generate and record a random [`", stringify!($t), "`] of a fixed bit length, see [`Acorn::", stringify!($fixed_bit_length), "`].")]
+            ///
+            #[doc = concat!("[`", stringify!($t), "`]: https://doc.rust-lang.org/core/primitive.", stringify!($t), ".html")]
+            #[doc = concat!("[`Acorn::", stringify!($fixed_bit_length), "`]: struct.Acorn.html#method.", stringify!($fixed_bit_length))]
+            pub fn $fixed_bit_length(&mut self, bits: u32) -> $t {
+                let position = self.prng.position();
+                let result = self.prng.$fixed_bit_length(bits);
+                self.record(Call::FixedBitLength(IntType::$int_type, bits), result as u128, position);
+                result
+            }
+        )*}
+        #[allow(clippy::cast_possible_truncation, clippy::cast_lossless)]
+        impl Replay<'_> {$(
+            #[doc = concat!("Replay a random [`", stringify!($t), "`] within a given [`RangeInclusive`], see [`Acorn::", stringify!($between_range), "`].")]
+            ///
+            /// # Errors
+            ///
+            /// Returns the first [Divergence](struct.Divergence.html) if this or any earlier call did not match.
+            ///
+            #[doc = concat!("[`", stringify!($t), "`]: https://doc.rust-lang.org/core/primitive.", stringify!($t), ".html")]
+            /// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+            #[doc = concat!("[`Acorn::", stringify!($between_range), "`]: struct.Acorn.html#method.", stringify!($between_range))]
+            pub const fn $between_range(&mut self, range: RangeInclusive<$t>) -> Result<$t, Divergence> {
+                match self.next_draw(Call::Range(IntType::$int_type, *range.start() as u128, *range.end() as u128)) {
+                    Ok(result) => Ok(result as $t),
+                    Err(divergence) => Err(divergence),
+                }
+            }
+            #[doc = concat!("Replay a random [`", stringify!($t), "`] of a fixed digit length, see [`Acorn::", stringify!($fixed_length), "`].")]
+            ///
+            /// # Errors
+            ///
+            /// Returns the first [Divergence](struct.Divergence.html) if this or any earlier call did not match.
+            ///
+            #[doc = concat!("[`", stringify!($t), "`]: https://doc.rust-lang.org/core/primitive.", stringify!($t), ".html")]
+            #[doc = concat!("[`Acorn::", stringify!($fixed_length), "`]: struct.Acorn.html#method.", stringify!($fixed_length))]
+            pub const fn $fixed_length(&mut self, length: usize) -> Result<$t, Divergence> {
+                match self.next_draw(Call::FixedLength(IntType::$int_type, length)) {
+                    Ok(result) => Ok(result as $t),
+                    Err(divergence) => Err(divergence),
+                }
+            }
+            #[doc = concat!("Replay a random [`", stringify!($t), "`] of a fixed bit length, see [`Acorn::", stringify!($fixed_bit_length), "`].")]
+            ///
+            /// # Errors
+            ///
+            /// Returns the first [Divergence](struct.Divergence.html) if this or any earlier call did not match.
+            ///
+            #[doc = concat!("[`", stringify!($t), "`]: https://doc.rust-lang.org/core/primitive.", stringify!($t), ".html")]
+            #[doc = concat!("[`Acorn::", stringify!($fixed_bit_length), "`]: struct.Acorn.html#method.", stringify!($fixed_bit_length))]
+            pub const fn $fixed_bit_length(&mut self, bits: u32) -> Result<$t, Divergence> {
+                match self.next_draw(Call::FixedBitLength(IntType::$int_type, bits)) {
+                    Ok(result) => Ok(result as $t),
+                    Err(divergence) => Err(divergence),
+                }
+            }
+        )*}
+    };
+}
+
+impl_typed! {
+    u8 => U8, generate_u8_between_range, generate_fixed_length_u8, generate_fixed_bit_length_u8;
+    u16 => U16, generate_u16_between_range, generate_fixed_length_u16, generate_fixed_bit_length_u16;
+    u32 => U32, generate_u32_between_range, generate_fixed_length_u32, generate_fixed_bit_length_u32;
+    u64 => U64, generate_u64_between_range, generate_fixed_length_u64, generate_fixed_bit_length_u64;
+    u128 => U128, generate_u128_between_range, generate_fixed_length_u128, generate_fixed_bit_length_u128;
+    usize => Usize, generate_usize_between_range, generate_fixed_length_usize, generate_fixed_bit_length_usize;
+}
+
+macro_rules! impl_signed_typed {
+    ($($t:ty => $int_type:ident, $fixed_bit_length:ident;)*) => {
+        #[allow(clippy::cast_sign_loss)]
+        impl<L: DrawLog> Recording<L> {$(
+            #[doc = concat!("Generate and record a random [`", stringify!($t), "`] of a fixed bit length, see [`Acorn::", stringify!($fixed_bit_length), "`].")]
+            ///
+            #[doc = concat!("[`", stringify!($t), "`]: https://doc.rust-lang.org/core/primitive.", stringify!($t), ".html")]
+            #[doc = concat!("[`Acorn::", stringify!($fixed_bit_length), "`]: struct.Acorn.html#method.", stringify!($fixed_bit_length))]
+            pub fn $fixed_bit_length(&mut self, bits: u32) -> $t {
+                let position = self.prng.position();
+                let result = self.prng.$fixed_bit_length(bits);
+                self.record(Call::SignedFixedBitLength(IntType::$int_type, bits), result as u128, position);
+                result
+            }
+        )*}
+        #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+        impl Replay<'_> {$(
+            #[doc = concat!("Replay a random [`", stringify!($t), "`] of a fixed bit length, see [`Acorn::", stringify!($fixed_bit_length), "`].")]
+            ///
+            /// # Errors
+            ///
+            /// Returns the first [Divergence](struct.Divergence.html) if this or any earlier call did not match.
+            ///
+            #[doc = concat!("[`", stringify!($t), "`]: https://doc.rust-lang.org/core/primitive.", stringify!($t), ".html")]
+            #[doc = concat!("[`Acorn::", stringify!($fixed_bit_length), "`]: struct.Acorn.html#method.", stringify!($fixed_bit_length))]
+            pub const fn $fixed_bit_length(&mut self, bits: u32) -> Result<$t, Divergence> {
+                match self.next_draw(Call::SignedFixedBitLength(IntType::$int_type, bits)) {
+                    Ok(result) => Ok(result as $t),
+                    Err(divergence) => Err(divergence),
+                }
+            }
+        )*}
+    };
+}
+
+impl_signed_typed! {
+    i8 => U8, generate_fixed_bit_length_i8;
+    i16 => U16, generate_fixed_bit_length_i16;
+    i32 => U32, generate_fixed_bit_length_i32;
+    i64 => U64, generate_fixed_bit_length_i64;
+    i128 => U128, generate_fixed_bit_length_i128;
+    isize => Usize, generate_fixed_bit_length_isize;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prng;
+    #[test]
+    fn encode_and_decode() {
+        let draws = [
+            Draw {call: Call::Raw, result: u128::MAX, steps: 1},
+            Draw {call: Call::Bits(300), result: 0, steps: 0},
+            Draw {call: Call::Range(IntType::Usize, u128::MAX, u128::MAX), result: 1 << 127, steps: u128::MAX},
+            Draw {call: Call::FixedLength(IntType::U16, 5), result: 12_345, steps: 2},
+            Draw {call: Call::FixedBitLength(IntType::U64, 64), result: 1 << 63, steps: 1},
+            Draw {call: Call::SignedFixedBitLength(IntType::U8, 3), result: (-5_i128).cast_unsigned(), steps: 1},
+            Draw {call: Call::Value(ValueType::Char), result: 'x'.to_recorded(), steps: 1},
+            Draw {call: Call::ValueRange(ValueType::F64, (-1.0_f64).to_recorded(), 1.0_f64.to_recorded()), result: 0, steps: 1},
+            Draw {call: Call::UuidV4, result: u128::MAX, steps: 1},
+            Draw {call: Call::UuidV7(u64::MAX), result: 7, steps: 1},
+            Draw {call: Call::Ulid(1_700_000_000_000), result: 1 << 100, steps: 1},
+        ];
+        let mut buffer = [0; Draw::MAX_BYTES];
+        for draw in draws {
+            let length = draw.encode(&mut buffer).unwrap();
+            assert_eq!(Draw::decode(&buffer[..length]), Some((draw, length)));
+            assert_eq!(Draw::decode(&buffer[..length - 1]), None);
+            assert_eq!(draw.encode(&mut buffer[..length - 1]), None);
+        }
+        assert_eq!(draws[2].encode(&mut buffer), Some(Draw::MAX_BYTES));
+        for value_type in ValueType::ALL {
+            let draw = Draw {call: Call::ValueRange(value_type, 1, 2), result: 1, steps: 1};
+            let length = draw.encode(&mut buffer).unwrap();
+            assert_eq!(Draw::decode(&buffer[..length]), Some((draw, length)));
+        }
+        assert_eq!(Draw::decode(&[61, 0, 0]), None); // unknown tag
+        assert_eq!(Draw::decode(&[0, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x04, 0]), None); // more than 128 bits
+    }
+    #[test]
+    fn record_and_replay() {
+        let mut buffer = [0; 1024];
+        let mut recording = Recording::new(prng(), SliceLog::new(&mut buffer));
+        let raw = recording.generate_u128();
+        let bits = recording.generate_bits(7);
+        let ranges = [recording.generate_u8_between_range(0..=10), recording.generate_u8_between_range(0..=10)];
+        let digits = recording.generate_fixed_length_u64(12);
+        let bit_length = recording.generate_fixed_bit_length_usize(9);
+        let signed = (recording.generate_fixed_bit_length_i8(8), recording.generate_fixed_bit_length_i64(40));
+        assert!(recording.is_complete());
+        let (recorded, log) = recording.into_parts();
+        let mut expected = prng();
+        assert_eq!(raw, expected.generate_u128());
+        assert_eq!(bits, expected.generate_bits(7));
+        assert_eq!(ranges, [expected.generate_u8_between_range(0..=10), expected.generate_u8_between_range(0..=10)]);
+        assert_eq!(recorded.position(), expected.position() + 4); // digits and bit lengths
+        let mut steps = 0;
+        let mut bytes = log.as_bytes();
+        while let Some((draw, length)) = Draw::decode(bytes) {
+            steps += draw.steps;
+            bytes = &bytes[length..];
+        }
+        assert_eq!(steps, recorded.position());
+        let mut replay = Replay::new(log.as_bytes());
+        assert_eq!(replay.generate_u128(), Ok(raw));
+        assert_eq!(replay.generate_bits(7), Ok(bits));
+        assert_eq!(replay.generate_u8_between_range(0..=10), Ok(ranges[0]));
+        assert_eq!(replay.generate_u8_between_range(0..=10), Ok(ranges[1]));
+        assert_eq!(replay.generate_fixed_length_u64(12), Ok(digits));
+        assert!(!replay.is_finished());
+        assert_eq!(replay.generate_fixed_bit_length_usize(9), Ok(bit_length));
+        assert_eq!(replay.generate_fixed_bit_length_i8(8), Ok(signed.0));
+        assert_eq!(replay.generate_fixed_bit_length_i64(40), Ok(signed.1));
+        assert!(replay.is_finished());
+        assert_eq!(replay.position(), recorded.position());
+        assert_eq!(replay.generate_u128(), Err(Divergence {index: 8, expected: None, actual: Call::Raw, mismatch: Mismatch::Call}));
+    }
+    #[test]
+    fn record_and_replay_values_and_ids() {
+        let mut buffer = [0; 1024];
+        let mut recording = Recording::new(prng(), SliceLog::new(&mut buffer));
+        let mut expected = prng();
+        let mut positions = [0; 6];
+        let signed = recording.generate_range(-20_i16..=-10);
+        positions[0] = recording.prng.position();
+        let float: f64 = recording.generate();
+        positions[1] = recording.prng.position();
+        let interval = recording.generate_range(-1.5_f32..=2.5);
+        positions[2] = recording.prng.position();
+        let (flag, letter): (bool, char) = (recording.generate(), recording.generate_range('a'..='z'));
+        positions[3] = recording.prng.position();
+        let wide: i128 = recording.generate();
+        positions[4] = recording.prng.position();
+        let ids = (recording.generate_uuid_v4(), recording.generate_uuid_v7(1), recording.generate_ulid(2));
+        positions[5] = recording.prng.position();
+        assert!(recording.is_complete());
+        assert_eq!(signed, expected.generate_range(-20_i16..=-10));
+        assert_eq!(float.to_bits(), expected.generate::<f64>().to_bits());
+        let (_, log) = recording.into_parts();
+        let mut replay = Replay::new(log.as_bytes());
+        assert_eq!(replay.generate_range(-20_i16..=-10), Ok(signed));
+        assert_eq!(replay.position(), positions[0]);
+        assert_eq!(replay.generate::<f64>().map(f64::to_bits), Ok(float.to_bits()));
+        assert_eq!(replay.position(), positions[1]);
+        assert_eq!(replay.generate_range(-1.5_f32..=2.5).map(f32::to_bits), Ok(interval.to_bits()));
+        assert_eq!(replay.position(), positions[2]);
+        assert_eq!((replay.generate(), replay.generate_range('a'..='z')), (Ok(flag), Ok(letter)));
+        assert_eq!(replay.position(), positions[3]);
+        assert_eq!(replay.generate(), Ok(wide));
+        assert_eq!(replay.position(), positions[4]);
+        assert_eq!(replay.generate_uuid_v4(), Ok(ids.0));
+        assert_eq!(replay.generate_uuid_v7(1), Ok(ids.1));
+        assert_eq!(replay.generate_ulid(2), Ok(ids.2));
+        assert_eq!(replay.position(), positions[5]);
+        assert!(replay.is_finished());
+    }
+    #[test]
+    fn check_results_and_steps() {
+        let mut buffer = [0; 64];
+        let mut recording = Recording::new(prng(), SliceLog::new(&mut buffer));
+        recording.generate_range(0.0..=1.0);
+        recording.generate_fixed_length_u64(12);
+        let (_, log) = recording.into_parts();
+        let mut check = Recording::new(prng(), Replay::new(log.as_bytes()));
+        check.generate_range(0.0..=1.0);
+        check.generate_fixed_length_u64(12);
+        assert!(check.is_complete());
+        let (prng, replay) = check.into_parts();
+        assert!(replay.is_finished());
+        assert_eq!(replay.position(), prng.position());
+        let result = self::prng().generate_u128();
+        let mut buffer = [0; Draw::MAX_BYTES];
+        let recorded = Draw {call: Call::Raw, result, steps: 2}; // the same result from a different number of steps
+        let length = recorded.encode(&mut buffer).unwrap();
+        let mut check = Recording::new(self::prng(), Replay::new(&buffer[..length]));
+        check.generate_u128();
+        assert!(!check.is_complete());
+        let divergence = Divergence {index: 0, expected: Some(Call::Raw), actual: Call::Raw, mismatch: Mismatch::Steps};
+        assert_eq!(check.into_parts().1.divergence(), Some(divergence));
+    }
+    #[test]
+    fn detect_divergence() {
+        let mut buffer = [0; 64];
+        let mut recording = Recording::new(prng(), SliceLog::new(&mut buffer));
+        let first = recording.generate_u32_between_range(1..=6);
+        recording.generate_u32_between_range(1..=6);
+        let (_, log) = recording.into_parts();
+        let mut replay = Replay::new(log.as_bytes());
+        assert_eq!(replay.generate_u32_between_range(1..=6), Ok(first));
+        let divergence = Divergence {
+            index: 1,
+            expected: Some(Call::Range(IntType::U32, 1, 6)),
+            actual: Call::Range(IntType::U64, 1, 6),
+            mismatch: Mismatch::Call,
+        };
+        assert_eq!(replay.generate_u64_between_range(1..=6), Err(divergence));
+        assert_eq!(replay.divergence(), Some(divergence));
+        assert_eq!(replay.generate_u32_between_range(1..=6), Err(divergence)); // later calls keep failing
+        assert_eq!(replay.index(), 1);
+    }
+    #[test]
+    fn full_slice_log() {
+        let mut buffer = [0; 8];
+        let mut recording = Recording::new(prng(), SliceLog::new(&mut buffer));
+        recording.generate_u8_between_range(1..=6);
+        assert!(recording.is_complete());
+        recording.generate_u128();
+        recording.generate_u8_between_range(1..=6); // would fit but must not follow a dropped draw
+        assert!(!recording.is_complete());
+        let (_, log) = recording.into_parts();
+        assert!(log.is_full());
+        assert_eq!(log.as_bytes().len(), 5);
+    }
+    #[cfg(feature = "std")]
+    #[test]
+    fn file_log() {
+        let path = std::env::temp_dir().join(std::format!("acorn_prng_draws_{}.bin", std::process::id()));
+        let file = std::fs::File::create(&path).unwrap();
+        let mut recording = Recording::new(prng(), IoLog::new(std::io::BufWriter::new(file)));
+        let numbers: std::vec::Vec<u16> = (0..100).map(|_| recording.generate_u16_between_range(0..=999)).collect();
+        let (_, log) = recording.into_parts();
+        log.into_inner().unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let mut replay = Replay::new(&bytes);
+        assert!(numbers.iter().all(|number| replay.generate_u16_between_range(0..=999) == Ok(*number)));
+        assert!(replay.is_finished());
+        let mut vector = std::vec::Vec::new();
+        let mut recording = Recording::new(prng(), IoLog::new(&mut vector));
+        recording.generate_u16_between_range(0..=999);
+        let mut in_memory = Recording::new(prng(), std::vec::Vec::new());
+        in_memory.generate_u16_between_range(0..=999);
+        drop(recording);
+        assert_eq!(in_memory.into_parts().1, vector);
+    }
+}
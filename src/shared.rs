@@ -0,0 +1,297 @@
+#[cfg(feature = "critical-section")]
+use core::cell::Cell;
+use core::cell::UnsafeCell;
+use core::ops::RangeInclusive;
+#[cfg(not(feature = "critical-section"))]
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::{Acorn, AcornFixedLength, AcornUniform};
+
+/// An [Acorn](struct.Acorn.html) generator that can be shared through a `static` between threads, or between a main
+/// loop and interrupt handlers.
+///
+/// Every method takes `&self` and holds a spin lock for the duration of a single call, so each draw is taken whole
+/// from the shared stream. Use [`with`](#method.with) to make several draws in one go.
+///
+/// With the `critical-section` feature the lock is a flag only touched inside [`critical_section::with`], which
+/// needs no atomic instructions, and every call holds the section until it returns so interrupt handlers and other
+/// cores wait rather than find the generator in use. Using the generator again from within its own section, for
+/// example from the closure passed to [`with`](#method.with), panics. Without the feature the lock is a spin lock
+/// on an atomic flag, which suits threads but deadlocks if an interrupt handler preempts a holder on the same core,
+/// so handlers should use [`try_with`](#method.try_with) instead. The spin lock needs 8-bit atomic compare and swap,
+/// so on targets without it, such as `thumbv6m-none-eabi` or `riscv32imc-unknown-none-elf`, this type is only
+/// available with the `critical-section` feature.
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::{Acorn, Order, Seed, SharedAcorn};
+///
+/// static PRNG: SharedAcorn = SharedAcorn::new(Acorn::new(Order::new(45), Seed::new(1_000_000)));
+///
+/// let roll = PRNG.generate_u8_between_range(1..=6);
+/// let pair = PRNG.with(|prng| (prng.generate_u8_between_range(1..=6), prng.generate_u8_between_range(1..=6)));
+///
+/// assert!((1..=6).contains(&roll));
+/// assert!((1..=6).contains(&pair.0) && (1..=6).contains(&pair.1));
+/// assert!(PRNG.with(|prng| prng.position()) >= 3);
+/// ```
+///
+/// [`critical_section::with`]: https://docs.rs/critical-section/latest/critical_section/fn.with.html
+#[derive(Debug)]
+pub struct SharedAcorn {
+    locked: Lock,
+    prng: UnsafeCell<Acorn>,
+}
+
+#[cfg(feature = "critical-section")]
+type Lock = critical_section::Mutex<Cell<bool>>;
+#[cfg(not(feature = "critical-section"))]
+type Lock = AtomicBool;
+
+// SAFETY: the generator is only reached while `locked` is held, and an Acorn is plain data that can be sent.
+unsafe impl Sync for SharedAcorn {}
+
+/// Releases the lock when dropped, including when the closure holding it panics.
+struct Guard<'a>(&'a Lock);
+
+impl Drop for Guard<'_> {
+    fn drop(&mut self) {
+        #[cfg(feature = "critical-section")]
+        critical_section::with(|cs| self.0.borrow(cs).set(false));
+        #[cfg(not(feature = "critical-section"))]
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+impl SharedAcorn {
+    /// Wrap an [Acorn](struct.Acorn.html) generator so it can be shared, for example in a `static`.
+    #[must_use]
+    pub const fn new(prng: Acorn) -> Self {
+        #[cfg(feature = "critical-section")]
+        let locked = critical_section::Mutex::new(Cell::new(false));
+        #[cfg(not(feature = "critical-section"))]
+        let locked = AtomicBool::new(false);
+        Self {locked, prng: UnsafeCell::new(prng)}
+    }
+    /// Unwrap the generator, which continues from the same position.
+    #[must_use]
+    pub fn into_inner(self) -> Acorn {
+        self.prng.into_inner()
+    }
+    /// Access the generator without locking, which is possible when nothing else can hold it.
+    pub const fn get_mut(&mut self) -> &mut Acorn {
+        self.prng.get_mut()
+    }
+    /// Call a function with exclusive access to the generator, waiting for the lock if it is held.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed, SharedAcorn};
+    ///
+    /// let shared = SharedAcorn::new(Acorn::new(Order::new(45), Seed::new(1_000_000)));
+    /// let mut deck = [0_u8; 52];
+    /// shared.with(|prng| deck.iter_mut().for_each(|card| *card = prng.generate_u8_between_range(0..=51)));
+    ///
+    /// assert!(shared.into_inner().position() >= 52); // out of range draws are rejected
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// With the `critical-section` feature, panics if the generator is already in use by the current section.
+    pub fn with<R>(&self, f: impl FnOnce(&mut Acorn) -> R) -> R {
+        #[cfg(feature = "critical-section")]
+        let result = self.try_with(f).expect("the shared generator is already in use");
+        #[cfg(not(feature = "critical-section"))]
+        let result = loop {
+            if let Some(guard) = self.lock() {
+                break f(self.borrow(&guard));
+            }
+            core::hint::spin_loop();
+        };
+        result
+    }
+    /// Call a function with exclusive access to the generator unless the lock is held, in which case [`None`] is
+    /// returned without waiting.
+    ///
+    /// With the `critical-section` feature the function runs inside a critical section like [`with`](#method.with),
+    /// so [`None`] is only returned when the generator is already in use by the current section.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed, SharedAcorn};
+    ///
+    /// let shared = SharedAcorn::new(Acorn::new(Order::new(45), Seed::new(1_000_000)));
+    ///
+    /// assert!(shared.try_with(|prng| prng.generate_u128()).is_some());
+    /// assert!(shared.try_with(|_| shared.try_with(|prng| prng.generate_u128())).unwrap().is_none());
+    /// ```
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    pub fn try_with<R>(&self, f: impl FnOnce(&mut Acorn) -> R) -> Option<R> {
+        #[cfg(feature = "critical-section")]
+        let result = critical_section::with(|_| self.lock().map(|guard| f(self.borrow(&guard))));
+        #[cfg(not(feature = "critical-section"))]
+        let result = self.lock().map(|guard| f(self.borrow(&guard)));
+        result
+    }
+    fn lock(&self) -> Option<Guard<'_>> {
+        #[cfg(feature = "critical-section")]
+        let acquired = critical_section::with(|cs| !self.locked.borrow(cs).replace(true));
+        #[cfg(not(feature = "critical-section"))]
+        let acquired = self.locked.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_ok();
+        acquired.then(|| Guard(&self.locked)) // a guard made on failure would release the lock when dropped
+    }
+    #[allow(clippy::mut_from_ref)] // exclusive while the guard is alive
+    fn borrow<'a>(&'a self, _guard: &'a Guard<'_>) -> &'a mut Acorn {
+        // SAFETY: holding the guard means no other reference to the generator exists until it is dropped.
+        unsafe {&mut *self.prng.get()}
+    }
+}
+
+impl From<Acorn> for SharedAcorn {
+    fn from(prng: Acorn) -> Self {
+        Self::new(prng)
+    }
+}
+
+macro_rules! shared_methods {
+    ($($method:ident($($argument:ident: $t:ty),*) -> $output:ty;)*) => {
+        impl SharedAcorn {$(
+            #[doc = concat!("Lock the generator for one call of [`Acorn::", stringify!($method), "`].")]
+            ///
+            #[doc = concat!("[`Acorn::", stringify!($method), "`]: struct.Acorn.html#method.", stringify!($method))]
+            pub fn $method(&self, $($argument: $t),*) -> $output {
+                self.with(|prng| prng.$method($($argument),*))
+            }
+        )*}
+    };
+}
+
+shared_methods! {
+    generate_u128() -> u128;
+    generate_bits(bits: u32) -> u128;
+    generate_fixed_length_usize(length: usize) -> usize;
+    generate_fixed_length_u8(length: usize) -> u8;
+    generate_fixed_length_u16(length: usize) -> u16;
+    generate_fixed_length_u32(length: usize) -> u32;
+    generate_fixed_length_u64(length: usize) -> u64;
+    generate_fixed_length_u128(length: usize) -> u128;
+    generate_fixed_bit_length_usize(bits: u32) -> usize;
+    generate_fixed_bit_length_u8(bits: u32) -> u8;
+    generate_fixed_bit_length_u16(bits: u32) -> u16;
+    generate_fixed_bit_length_u32(bits: u32) -> u32;
+    generate_fixed_bit_length_u64(bits: u32) -> u64;
+    generate_fixed_bit_length_u128(bits: u32) -> u128;
+    generate_fixed_bit_length_isize(bits: u32) -> isize;
+    generate_fixed_bit_length_i8(bits: u32) -> i8;
+    generate_fixed_bit_length_i16(bits: u32) -> i16;
+    generate_fixed_bit_length_i32(bits: u32) -> i32;
+    generate_fixed_bit_length_i64(bits: u32) -> i64;
+    generate_fixed_bit_length_i128(bits: u32) -> i128;
+    generate_usize_between_range(range: RangeInclusive<usize>) -> usize;
+    generate_u8_between_range(range: RangeInclusive<u8>) -> u8;
+    generate_u16_between_range(range: RangeInclusive<u16>) -> u16;
+    generate_u32_between_range(range: RangeInclusive<u32>) -> u32;
+    generate_u64_between_range(range: RangeInclusive<u64>) -> u64;
+    generate_u128_between_range(range: RangeInclusive<u128>) -> u128;
+}
+
+impl SharedAcorn {
+    /// Lock the generator for one call of [`Acorn::generate`].
+    ///
+    /// [`Acorn::generate`]: struct.Acorn.html#method.generate
+    pub fn generate<T: AcornUniform>(&self) -> T {
+        self.with(Acorn::generate)
+    }
+    /// Lock the generator for one call of [`Acorn::generate_range`].
+    ///
+    /// [`Acorn::generate_range`]: struct.Acorn.html#method.generate_range
+    pub fn generate_range<T: AcornUniform>(&self, range: RangeInclusive<T>) -> T {
+        self.with(|prng| prng.generate_range(range))
+    }
+    /// Lock the generator for one call of [`Acorn::generate_fixed_length`].
+    ///
+    /// [`Acorn::generate_fixed_length`]: struct.Acorn.html#method.generate_fixed_length
+    pub fn generate_fixed_length<T: AcornFixedLength>(&self, length: usize) -> T {
+        self.with(|prng| prng.generate_fixed_length(length))
+    }
+    /// Lock the generator for one call of [`Acorn::generate_fixed_bit_length`].
+    ///
+    /// [`Acorn::generate_fixed_bit_length`]: struct.Acorn.html#method.generate_fixed_bit_length
+    pub fn generate_fixed_bit_length<T: AcornFixedLength>(&self, bits: u32) -> T {
+        self.with(|prng| prng.generate_fixed_bit_length(bits))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prng, Order, Seed};
+    #[test]
+    fn same_stream_as_acorn() {
+        let shared = SharedAcorn::new(prng());
+        let mut expected = prng();
+        assert_eq!(shared.generate_u128(), expected.generate_u128());
+        assert_eq!(shared.generate_u8_between_range(1..=6), expected.generate_u8_between_range(1..=6));
+        assert_eq!(shared.generate_fixed_length_u64(7), expected.generate_fixed_length_u64(7));
+        assert_eq!(shared.generate_fixed_bit_length_i16(9), expected.generate_fixed_bit_length_i16(9));
+        assert_eq!(shared.generate_range(-5_i32..=5), expected.generate_range(-5_i32..=5));
+        assert_eq!(shared.generate::<f64>().to_bits(), expected.generate::<f64>().to_bits());
+        assert_eq!(shared.generate_fixed_length::<u32>(4), expected.generate_fixed_length::<u32>(4));
+        assert_eq!(SharedAcorn::from(prng()).with(|prng| prng.generate_bits(7)), prng().generate_bits(7));
+        let mut shared = shared;
+        assert_eq!(shared.get_mut().generate_u128(), expected.generate_u128());
+        assert_eq!(shared.into_inner(), expected);
+    }
+    #[test]
+    fn lock_is_released() {
+        let shared = SharedAcorn::new(prng());
+        assert_eq!(shared.try_with(|_| shared.try_with(|_| ())), Some(None));
+        assert_eq!(shared.try_with(|_| [shared.try_with(|_| ()), shared.try_with(|_| ())]), Some([None, None]));
+        assert_eq!(shared.try_with(|_| ()), Some(()));
+        let unwound = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| shared.with(|_| panic!("draw"))));
+        assert!(unwound.is_err());
+        assert_eq!(shared.try_with(|prng| prng.position()), Some(0));
+    }
+    #[test]
+    fn shared_between_threads() {
+        static SHARED: SharedAcorn = SharedAcorn::new(Acorn::new(Order::new(45), Seed::new(1_000_000)));
+        let handles: [_; 4] = core::array::from_fn(|_| std::thread::spawn(|| {
+            (0..250).map(|_| SHARED.generate_u128()).collect::<std::vec::Vec<_>>()
+        }));
+        let mut drawn: std::vec::Vec<u128> = handles.into_iter().flat_map(|handle| handle.join().unwrap()).collect();
+        let mut expected: std::vec::Vec<u128> = {
+            let mut prng = prng();
+            (0..1_000).map(|_| prng.generate_u128()).collect()
+        };
+        drawn.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(drawn, expected); // every output drawn exactly once
+    }
+    #[cfg(feature = "critical-section")]
+    #[test]
+    #[should_panic(expected = "already in use")]
+    fn nested_use_panics() {
+        let shared = SharedAcorn::new(prng());
+        shared.with(|_| shared.generate_u128());
+    }
+    #[cfg(feature = "critical-section")]
+    #[test]
+    fn concurrent_use_waits() {
+        static SHARED: SharedAcorn = SharedAcorn::new(Acorn::new(Order::new(45), Seed::new(1_000_000)));
+        let holder = std::thread::spawn(|| SHARED.try_with(|prng| {
+            std::thread::sleep(std::time::Duration::from_millis(50));
+            prng.generate_u128()
+        }));
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        let mut drawn = [SHARED.generate_u128(), holder.join().unwrap().unwrap()];
+        let mut prng = prng();
+        let mut expected = [prng.generate_u128(), prng.generate_u128()];
+        drawn.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(drawn, expected);
+    }
+}
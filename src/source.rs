@@ -0,0 +1,332 @@
+use core::ops::RangeInclusive;
+
+use crate::{generate_below, Acorn, NumType, SignedBounds, Version};
+
+macro_rules! random_source_methods {
+    ($($t:ty => $num_type:ident, $max_length:literal, $between_range:ident, $fixed_length:ident,
+        $fixed_bit_length:ident, $signed:ty, $signed_fixed_bit_length:ident;)*) => {$(
+        #[doc = concat!("Generate a random [`", stringify!($t), "`] within a given [`RangeInclusive`], see [`Acorn::",
+            stringify!($between_range), "`].")]
+        ///
+        #[doc = concat!("[`", stringify!($t), "`]: https://doc.rust-lang.org/core/primitive.", stringify!($t), ".html")]
+        /// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+        #[doc = concat!("[`Acorn::", stringify!($between_range), "`]: struct.Acorn.html#method.",
+            stringify!($between_range))]
+        #[allow(clippy::cast_possible_truncation, clippy::cast_lossless)]
+        fn $between_range(&mut self, range: RangeInclusive<$t>) -> $t {
+            self.generate_u128_between_range(*range.start() as u128..=*range.end() as u128) as $t
+        }
+        #[doc = concat!("Generate a random [`", stringify!($t), "`] of a fixed digit length, see [`Acorn::",
+            stringify!($fixed_length), "`].")]
+        ///
+        #[doc = concat!("[`", stringify!($t), "`]: https://doc.rust-lang.org/core/primitive.", stringify!($t), ".html")]
+        #[doc = concat!("[`Acorn::", stringify!($fixed_length), "`]: struct.Acorn.html#method.",
+            stringify!($fixed_length))]
+        #[allow(clippy::cast_possible_truncation)]
+        fn $fixed_length(&mut self, length: usize) -> $t {
+            let (lower_bound, upper_bound) = Acorn::generate_clamped_bounds(length, $max_length, &NumType::$num_type);
+            self.generate_u128_between_range(lower_bound..=upper_bound) as $t
+        }
+        #[doc = concat!("Generate a random [`", stringify!($t), "`] of a fixed bit length, see [`Acorn::",
+            stringify!($fixed_bit_length), "`].")]
+        ///
+        #[doc = concat!("[`", stringify!($t), "`]: https://doc.rust-lang.org/core/primitive.", stringify!($t), ".html")]
+        #[doc = concat!("[`Acorn::", stringify!($fixed_bit_length), "`]: struct.Acorn.html#method.",
+            stringify!($fixed_bit_length))]
+        #[allow(clippy::cast_possible_truncation)]
+        fn $fixed_bit_length(&mut self, bits: u32) -> $t {
+            let (lower_bound, upper_bound) = Acorn::generate_clamped_bit_bounds(bits, <$t>::BITS);
+            self.generate_u128_between_range(lower_bound..=upper_bound) as $t
+        }
+        #[doc = concat!("Generate a random [`", stringify!($signed), "`] whose absolute value has a fixed bit length, ",
+            "see [`Acorn::", stringify!($signed_fixed_bit_length), "`].")]
+        ///
+        #[doc = concat!("[`", stringify!($signed), "`]: https://doc.rust-lang.org/core/primitive.", stringify!($signed),
+            ".html")]
+        #[doc = concat!("[`Acorn::", stringify!($signed_fixed_bit_length), "`]: struct.Acorn.html#method.",
+            stringify!($signed_fixed_bit_length))]
+        #[allow(clippy::cast_possible_truncation, clippy::cast_lossless)]
+        fn $signed_fixed_bit_length(&mut self, bits: u32) -> $signed {
+            generate_fixed_bit_length_signed(self, bits, <$signed>::MAX.unsigned_abs() as u128) as $signed
+        }
+    )*};
+}
+
+/// A source of uniformly distributed raw outputs that the sampling methods are built on.
+///
+/// Only [`next_u128`] and [`output_bits`] have to be implemented. Every other method is provided on top of them
+/// with the same algorithms as the methods of the same name on an [Acorn](struct.Acorn.html) of
+/// [`Version::V3`](enum.Version.html#variant.V3) or later, so helpers that are generic over a source can be fed by
+/// an [Acorn](struct.Acorn.html), a [scripted source](struct.ScriptedSource.html) in unit tests or another engine.
+///
+/// Every ranged method goes through [`generate_u128_between_range`], so overriding it changes how all of them sample.
+/// [Acorn](struct.Acorn.html) does this to keep the range method of its [Version](enum.Version.html), so its
+/// provided methods always match its inherent methods.
+///
+/// The provided methods share their digit bounds, bit bounds, sign handling and the loops that combine and reject raw
+/// outputs with the inherent methods of [Acorn](struct.Acorn.html). [`AcornUniform`], strings and IDs take an
+/// [Acorn](struct.Acorn.html) rather than any source, since strings and IDs are `const fn` as well and all of their
+/// streams are defined by its [Version](enum.Version.html); build such values from the methods of this trait to stay
+/// generic.
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::{Acorn, Order, RandomSource, ScriptedSource, Seed};
+///
+/// fn roll<S: RandomSource>(source: &mut S) -> u8 {
+///     source.generate_u8_between_range(1..=6)
+/// }
+///
+/// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+/// assert!((1..=6).contains(&roll(&mut prng)));
+///
+/// let mut scripted = ScriptedSource::new(&[255], 8); // the highest possible 8 bit output
+/// assert_eq!(roll(&mut scripted), 6);
+/// ```
+///
+/// [`next_u128`]: trait.RandomSource.html#tymethod.next_u128
+/// [`output_bits`]: trait.RandomSource.html#tymethod.output_bits
+/// [`generate_u128_between_range`]: trait.RandomSource.html#method.generate_u128_between_range
+/// [`AcornUniform`]: trait.AcornUniform.html
+pub trait RandomSource {
+    /// Generate the next raw output, which must be uniform over the lowest [`output_bits`] bits.
+    ///
+    /// [`output_bits`]: trait.RandomSource.html#tymethod.output_bits
+    fn next_u128(&mut self) -> u128;
+    /// The number of meaningful bits of each raw output, between 1 and 128.
+    ///
+    /// This takes `self` because the width of an [Acorn](struct.Acorn.html) depends on its
+    /// [Modulus](struct.Modulus.html).
+    fn output_bits(&self) -> u32;
+    /// Generate a random number of up to 128 `bits` taken from the highest bits of as many raw outputs as needed,
+    /// see [`Acorn::generate_bits`].
+    ///
+    /// [`Acorn::generate_bits`]: struct.Acorn.html#method.generate_bits
+    fn generate_bits(&mut self, bits: u32) -> u128 {
+        let bits = bits.min(128);
+        let width = self.output_bits();
+        let mut number = 0_u128;
+        let mut filled = 0;
+        while filled < bits {
+            let take = (bits - filled).min(width);
+            number = number.checked_shl(take).unwrap_or(0) | (self.next_u128() >> (width - take));
+            filled += take;
+        }
+        number
+    }
+    /// Generate a random [`u128`] within a given [`RangeInclusive`] with Lemire's multiply-and-reject method, see
+    /// [`Acorn::generate_u128_between_range`].
+    ///
+    /// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+    /// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+    /// [`Acorn::generate_u128_between_range`]: struct.Acorn.html#method.generate_u128_between_range
+    fn generate_u128_between_range(&mut self, range: RangeInclusive<u128>) -> u128 {
+        let (start, end) = (*range.start(), *range.end());
+        generate_below!(multiplied self.output_bits(), end - start, || self.next_u128(), |bits| self.generate_bits(bits))
+            + start
+    }
+    random_source_methods! {
+        u8 => U8, 3, generate_u8_between_range, generate_fixed_length_u8, generate_fixed_bit_length_u8,
+            i8, generate_fixed_bit_length_i8;
+        u16 => U16, 5, generate_u16_between_range, generate_fixed_length_u16, generate_fixed_bit_length_u16,
+            i16, generate_fixed_bit_length_i16;
+        u32 => U32, 10, generate_u32_between_range, generate_fixed_length_u32, generate_fixed_bit_length_u32,
+            i32, generate_fixed_bit_length_i32;
+        u64 => U64, 20, generate_u64_between_range, generate_fixed_length_u64, generate_fixed_bit_length_u64,
+            i64, generate_fixed_bit_length_i64;
+        usize => Usize, 39, generate_usize_between_range, generate_fixed_length_usize,
+            generate_fixed_bit_length_usize, isize, generate_fixed_bit_length_isize;
+    }
+    /// Generate a random [`u128`] of a fixed digit length, see [`Acorn::generate_fixed_length_u128`].
+    ///
+    /// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+    /// [`Acorn::generate_fixed_length_u128`]: struct.Acorn.html#method.generate_fixed_length_u128
+    fn generate_fixed_length_u128(&mut self, length: usize) -> u128 {
+        let (lower_bound, upper_bound) = Acorn::generate_clamped_bounds(length, 39, &NumType::U128);
+        self.generate_u128_between_range(lower_bound..=upper_bound)
+    }
+    /// Generate a random [`u128`] of a fixed bit length, see [`Acorn::generate_fixed_bit_length_u128`].
+    ///
+    /// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+    /// [`Acorn::generate_fixed_bit_length_u128`]: struct.Acorn.html#method.generate_fixed_bit_length_u128
+    fn generate_fixed_bit_length_u128(&mut self, bits: u32) -> u128 {
+        let (lower_bound, upper_bound) = Acorn::generate_clamped_bit_bounds(bits, 128);
+        self.generate_u128_between_range(lower_bound..=upper_bound)
+    }
+    /// Generate a random [`i128`] whose absolute value has a fixed bit length, see
+    /// [`Acorn::generate_fixed_bit_length_i128`].
+    ///
+    /// [`i128`]: https://doc.rust-lang.org/core/primitive.i128.html
+    /// [`Acorn::generate_fixed_bit_length_i128`]: struct.Acorn.html#method.generate_fixed_bit_length_i128
+    fn generate_fixed_bit_length_i128(&mut self, bits: u32) -> i128 {
+        generate_fixed_bit_length_signed(self, bits, i128::MAX.unsigned_abs())
+    }
+}
+
+/// Generate a signed number whose absolute value has a fixed bit length and whose positive values are at most
+/// `max`, see [`Acorn::generate_fixed_bit_length_i128`](struct.Acorn.html#method.generate_fixed_bit_length_i128).
+fn generate_fixed_bit_length_signed<S: RandomSource + ?Sized>(source: &mut S, bits: u32, max: u128) -> i128 {
+    let (lower_bound, upper_bound) = Acorn::generate_clamped_bit_bounds(bits, 129 - max.leading_zeros());
+    let signed = SignedBounds::new(lower_bound, upper_bound, max);
+    signed.value(source.generate_u128_between_range(0..=signed.last_index))
+}
+
+/// Generate a number within a range the way an [Acorn](struct.Acorn.html) of the given [Version](enum.Version.html)
+/// does, for other engines that override [`RandomSource::generate_u128_between_range`] to produce the same stream.
+///
+/// [`RandomSource::generate_u128_between_range`]: trait.RandomSource.html#method.generate_u128_between_range
+pub(crate) fn generate_versioned_range<S: RandomSource + ?Sized>(source: &mut S, version: Version,
+    range: RangeInclusive<u128>) -> u128 {
+    let (start, upper_bound) = (*range.start(), *range.end() - *range.start());
+    generate_below!(versioned version, source.output_bits(), upper_bound, || source.next_u128(),
+        |bits| source.generate_bits(bits)) + start
+}
+
+impl RandomSource for Acorn {
+    fn next_u128(&mut self) -> u128 {
+        Acorn::generate_u128(self)
+    }
+    fn output_bits(&self) -> u32 {
+        self.m.trailing_zeros()
+    }
+    fn generate_bits(&mut self, bits: u32) -> u128 {
+        Acorn::generate_bits(self, bits)
+    }
+    fn generate_u128_between_range(&mut self, range: RangeInclusive<u128>) -> u128 {
+        Acorn::generate_u128_between_range(self, range) // keeps the range method of the version
+    }
+}
+
+/// A [`RandomSource`](trait.RandomSource.html) that replays a fixed script of raw outputs, for forcing specific
+/// values in unit tests.
+///
+/// The outputs are masked to the given number of bits and repeat once the script is exhausted. An empty script
+/// always produces 0. Note that a ranged method loops forever if every output of the script is rejected.
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::{RandomSource, ScriptedSource};
+///
+/// let mut source = ScriptedSource::new(&[0, u128::MAX], 4);
+///
+/// assert_eq!(source.generate_bits(8), 0x0F); // two outputs of 4 bits
+/// assert_eq!(source.generate_u8_between_range(10..=25), 10);
+/// assert_eq!(source.consumed(), 3);
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ScriptedSource<'a> {
+    outputs: &'a [u128],
+    bits: u32,
+    index: usize,
+}
+
+impl<'a> ScriptedSource<'a> {
+    /// Create a source producing `outputs` as raw outputs of `bits` bits.
+    ///
+    /// Note that `bits` is clamped between 1 and 128.
+    #[must_use]
+    pub const fn new(outputs: &'a [u128], bits: u32) -> Self {
+        let bits = if bits == 0 {1} else if bits > 128 {128} else {bits};
+        Self {outputs, bits, index: 0}
+    }
+    /// The number of raw outputs produced so far.
+    #[must_use]
+    pub const fn consumed(&self) -> usize {
+        self.index
+    }
+}
+
+impl RandomSource for ScriptedSource<'_> {
+    fn next_u128(&mut self) -> u128 {
+        if self.outputs.is_empty() {return 0;}
+        let output = self.outputs[self.index % self.outputs.len()];
+        self.index += 1;
+        output & (u128::MAX >> (128 - self.bits))
+    }
+    fn output_bits(&self) -> u32 {
+        self.bits
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{prng, AcornBuilder, Order, Seed};
+    /// Draw every kind of number through the trait only.
+    fn draw_all<S: RandomSource>(source: &mut S) -> [u128; 12] {
+        [
+            source.generate_bits(77),
+            source.generate_u8_between_range(3..=200).into(),
+            source.generate_u64_between_range(0..=u64::MAX).into(),
+            source.generate_u128_between_range(5..=u128::MAX),
+            source.generate_fixed_length_u16(4).into(),
+            source.generate_fixed_length_usize(12) as u128,
+            source.generate_fixed_length_u128(39),
+            source.generate_fixed_bit_length_u32(17).into(),
+            source.generate_fixed_bit_length_i8(8).cast_unsigned().into(),
+            source.generate_fixed_bit_length_i64(33).cast_unsigned().into(),
+            source.generate_fixed_bit_length_i128(100).cast_unsigned(),
+            source.generate_fixed_bit_length_isize(1).cast_unsigned() as u128,
+        ]
+    }
+    #[test]
+    fn acorn_matches_inherent_methods() {
+        let generators: [fn() -> Acorn; 3] = [prng, || Acorn::with_version(Order::new(45), Seed::new(1_000_000),
+            Version::V1), || AcornBuilder::new().modulus(30).build()];
+        for generator in generators {
+            let (mut prng, mut expected) = (generator(), generator());
+            let numbers = draw_all(&mut prng);
+            assert_eq!(numbers, [
+                expected.generate_bits(77),
+                expected.generate_u8_between_range(3..=200).into(),
+                expected.generate_u64_between_range(0..=u64::MAX).into(),
+                expected.generate_u128_between_range(5..=u128::MAX),
+                expected.generate_fixed_length_u16(4).into(),
+                expected.generate_fixed_length_usize(12) as u128,
+                expected.generate_fixed_length_u128(39),
+                expected.generate_fixed_bit_length_u32(17).into(),
+                expected.generate_fixed_bit_length_i8(8).cast_unsigned().into(),
+                expected.generate_fixed_bit_length_i64(33).cast_unsigned().into(),
+                expected.generate_fixed_bit_length_i128(100).cast_unsigned(),
+                expected.generate_fixed_bit_length_isize(1).cast_unsigned() as u128,
+            ]);
+            assert_eq!(prng, expected);
+        }
+    }
+    #[test]
+    fn provided_methods_match_acorn() {
+        /// Forwards only the raw outputs so every provided method is used.
+        struct Raw(Acorn);
+        impl RandomSource for Raw {
+            fn next_u128(&mut self) -> u128 {
+                self.0.generate_u128()
+            }
+            fn output_bits(&self) -> u32 {
+                self.0.output_bits()
+            }
+        }
+        let generators: [fn() -> Acorn; 2] = [|| Acorn::with_version(Order::new(45), Seed::new(1_000_000), Version::V3),
+            || AcornBuilder::new().modulus(30).version(Version::V4).build()];
+        for generator in generators {
+            assert_eq!(draw_all(&mut Raw(generator())), draw_all(&mut generator()));
+        }
+    }
+    #[test]
+    fn scripted_outputs() {
+        let mut source = ScriptedSource::new(&[1 << 7, 3 << 6, 255], 8);
+        assert_eq!(source.generate_u8_between_range(0..=1), 1);
+        assert_eq!(source.generate_u8_between_range(0..=3), 3);
+        assert_eq!(source.generate_bits(8), 255);
+        assert_eq!(source.generate_bits(16), 0x80C0);
+        assert_eq!(source.consumed(), 5);
+        let mut rejected = ScriptedSource::new(&[171, 172], 8); // 171 would be biased towards 4
+        assert_eq!(rejected.generate_u8_between_range(1..=6), 5);
+        assert_eq!(rejected.consumed(), 2);
+        let mut empty = ScriptedSource::new(&[], 0);
+        assert_eq!(empty.output_bits(), 1);
+        assert_eq!(empty.generate_u128_between_range(7..=8), 7);
+        assert_eq!(ScriptedSource::new(&[u128::MAX], 500).generate_bits(128), u128::MAX);
+    }
+}
@@ -0,0 +1,204 @@
+use crate::{Acorn, Modulus, Order, Seed, Version};
+
+/// The registers and bookkeeping shared by every engine, generic over the word the registers are stored in.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) struct State<W> {
+    pub(crate) y: (W, W),
+    pub(crate) seed: Option<Seed>,
+    pub(crate) version: Version,
+    pub(crate) warmup: usize,
+    pub(crate) start: (W, W),
+    pub(crate) position: u128,
+}
+
+/// Run one step of the recurrence on a [`State`] of any word type and return the output register.
+///
+/// The addition reduced by the modulus is passed as an expression of the two named registers. This is a macro
+/// rather than a generic function because const fn cannot call trait methods.
+macro_rules! step {
+    ($state:expr, $order:expr, |$previous:ident, $current:ident| $add:expr) => {{
+        let state = $state;
+        let order = $order;
+        let (mut $previous, mut $current) = state.y;
+        let mut index = 1;
+        while index < order {
+            let result = $add;
+            if index % 2 == 0 {
+                state.y.1 = $previous;
+                $current = state.y.0;
+            } else {
+                state.y.0 = $previous;
+                $current = state.y.1;
+            }
+            $previous = result;
+            index += 1;
+        }
+        state.position = state.position.wrapping_add(1);
+        if order.is_multiple_of(2) {state.y.0} else {state.y.1}
+    }};
+}
+
+pub(crate) use step;
+
+/// The first byte of every serialised state, changed whenever the layout changes.
+const FORMAT: u8 = 1;
+
+const fn write_u128(bytes: &mut [u8], offset: usize, value: u128) {
+    let value = value.to_le_bytes();
+    let mut index = 0;
+    while index < 16 {
+        bytes[offset + index] = value[index];
+        index += 1;
+    }
+}
+
+const fn read_u128(bytes: &[u8], offset: usize) -> u128 {
+    let mut value = [0; 16];
+    let mut index = 0;
+    while index < 16 {
+        value[index] = bytes[offset + index];
+        index += 1;
+    }
+    u128::from_le_bytes(value)
+}
+
+impl Acorn {
+    /// The number of bytes of a state serialised by [`to_bytes`](struct.Acorn.html#method.to_bytes).
+    pub const STATE_BYTES: usize = 104;
+    /// Serialise the complete state of this generator, including its [position](struct.Acorn.html#method.position).
+    ///
+    /// The layout is stable and every number is little-endian:
+    ///
+    /// | Bytes    | Content                                              |
+    /// |----------|------------------------------------------------------|
+    /// | 0        | format, currently 1                                  |
+    /// | 1        | stream [Version](enum.Version.html), 1 to 4          |
+    /// | 2..4     | [Order](struct.Order.html) as a [`u16`]              |
+    /// | 4        | [Modulus](struct.Modulus.html) as a power of two     |
+    /// | 5..7     | warm-up length as a [`u16`]                          |
+    /// | 7        | 1 if the [Seed](struct.Seed.html) follows, otherwise 0 |
+    /// | 8..24    | [Seed](struct.Seed.html)                             |
+    /// | 24..56   | current registers                                    |
+    /// | 56..88   | registers at position 0                              |
+    /// | 88..104  | position                                             |
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// prng.generate_u128();
+    /// let bytes = prng.to_bytes();
+    /// let mut restored = Acorn::from_bytes(&bytes).unwrap();
+    ///
+    /// assert_eq!(restored.position(), 1);
+    /// assert_eq!(restored.generate_u128(), prng.generate_u128());
+    /// ```
+    ///
+    /// [`u16`]: https://doc.rust-lang.org/core/primitive.u16.html
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn to_bytes(&self) -> [u8; Acorn::STATE_BYTES] {
+        let mut bytes = [0; Acorn::STATE_BYTES];
+        bytes[0] = FORMAT;
+        bytes[1] = match self.state.version {
+            Version::V1 => 1,
+            Version::V2 => 2,
+            Version::V3 => 3,
+            Version::V4 => 4,
+        };
+        let order = (self.k.get() as u16).to_le_bytes();
+        (bytes[2], bytes[3]) = (order[0], order[1]);
+        bytes[4] = self.m.trailing_zeros() as u8;
+        let warmup = (self.state.warmup as u16).to_le_bytes();
+        (bytes[5], bytes[6]) = (warmup[0], warmup[1]);
+        if let Some(seed) = self.state.seed {
+            bytes[7] = 1;
+            write_u128(&mut bytes, 8, seed.get());
+        }
+        write_u128(&mut bytes, 24, self.state.y.0);
+        write_u128(&mut bytes, 40, self.state.y.1);
+        write_u128(&mut bytes, 56, self.state.start.0);
+        write_u128(&mut bytes, 72, self.state.start.1);
+        write_u128(&mut bytes, 88, self.state.position);
+        bytes
+    }
+    /// Restore a generator from a state serialised by [`to_bytes`](struct.Acorn.html#method.to_bytes).
+    ///
+    /// Returns [`None`] if the bytes are not a valid state, for example because the format is unknown, a
+    /// setting is outside of the range a generator can be created with or a register is not below the modulus.
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    #[must_use]
+    pub const fn from_bytes(bytes: &[u8; Acorn::STATE_BYTES]) -> Option<Self> {
+        let version = match bytes[1] {
+            1 => Version::V1,
+            2 => Version::V2,
+            3 => Version::V3,
+            4 => Version::V4,
+            _ => return None,
+        };
+        let order = u16::from_le_bytes([bytes[2], bytes[3]]) as usize;
+        let bits = bytes[4] as u32;
+        let warmup = u16::from_le_bytes([bytes[5], bytes[6]]) as usize;
+        let seed = match bytes[7] {
+            0 => None,
+            1 => Some(Seed::new_unclamped(read_u128(bytes, 8))),
+            _ => return None,
+        };
+        if bytes[0] != FORMAT || Order::new(order).get() != order || Modulus::new(bits).bits() != bits {return None;}
+        let m = 2_u128.pow(bits);
+        let y = (read_u128(bytes, 24), read_u128(bytes, 40));
+        let start = (read_u128(bytes, 56), read_u128(bytes, 72));
+        if y.0 >= m || y.1 >= m || start.0 >= m || start.1 >= m {return None;}
+        let mut acorn = Acorn::initialise(Order::new(order), m, seed, version, start, 0);
+        acorn.state.warmup = warmup;
+        acorn.state.y = y;
+        acorn.state.position = read_u128(bytes, 88);
+        Some(acorn)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AcornBuilder;
+    #[test]
+    fn state_round_trip() {
+        let generators = [
+            Acorn::new(Order::new(45), Seed::new(1_000_000)),
+            AcornBuilder::new().order(1_000).seed(u128::MAX).warmup(300).modulus(30).version(Version::V4).build(),
+            Acorn::with_extended_seed(Order::new(100), &[1, 2, 3]),
+        ];
+        for mut prng in generators {
+            for _ in 0..10 {prng.generate_u32_between_range(0..=1_000);}
+            let mut restored = Acorn::from_bytes(&prng.to_bytes()).unwrap();
+            assert_eq!(restored, prng);
+            assert_eq!(restored.generate_u128(), prng.generate_u128());
+            restored.reset();
+            prng.reset();
+            assert_eq!(restored.generate_u128(), prng.generate_u128());
+            restored.reseed(Seed::new(5_000_000));
+            prng.reseed(Seed::new(5_000_000));
+            assert_eq!(restored, prng);
+        }
+    }
+    #[test]
+    fn invalid_states() {
+        let bytes = Acorn::new(Order::new(45), Seed::new(1_000_000)).to_bytes();
+        let invalid = |index: usize, value: u8| {
+            let mut bytes = bytes;
+            bytes[index] = value;
+            Acorn::from_bytes(&bytes)
+        };
+        assert_eq!(invalid(0, 2), None); // format
+        assert_eq!(invalid(1, 5), None); // version
+        assert_eq!(invalid(2, 1), None); // order 1
+        assert_eq!(invalid(4, 121), None); // modulus
+        assert_eq!(invalid(7, 2), None); // seed flag
+        assert_eq!(invalid(4, 64), None); // registers no longer below the modulus
+        assert!(invalid(7, 0).is_some_and(|prng| prng.initial_seed().is_none()));
+        assert_eq!(Acorn::from_bytes(&[0; Acorn::STATE_BYTES]), None);
+    }
+}
@@ -0,0 +1,211 @@
+use crate::Acorn;
+
+/// A set of ASCII characters that random strings are drawn from.
+///
+/// Every character is equally likely, so an alphabet containing a character twice makes it twice as likely.
+///
+/// The strings generated from these charsets are **not secret**. Anyone who knows or guesses the seed can
+/// reproduce every string, so never use them as passwords, session tokens, API keys or anything else that must
+/// not be guessed. They are intended for test data and identifiers that only need to be unique.
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::{Acorn, Charset, Order, Seed};
+///
+/// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+/// let mut buffer = [0; 12];
+/// let id = prng.fill_string(&mut buffer, Charset::ALPHANUMERIC);
+/// let vowels = Charset::new(b"aeiou").unwrap();
+///
+/// assert!(id.chars().all(|c| c.is_ascii_alphanumeric()));
+/// assert!(prng.generate_string_array::<5>(vowels).iter().all(|c| b"aeiou".contains(c)));
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Charset<'a>(&'a [u8]);
+
+impl Charset<'static> {
+    /// The digits followed by the uppercase and lowercase letters.
+    pub const ALPHANUMERIC: Self = Self(b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz");
+    /// The [RFC 4648](https://www.rfc-editor.org/rfc/rfc4648#section-6) base 32 alphabet.
+    pub const BASE32: Self = Self(b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567");
+    /// The Bitcoin base 58 alphabet, which leaves out the easily confused `0`, `O`, `I` and `l`.
+    pub const BASE58: Self = Self(b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz");
+    /// The [RFC 4648](https://www.rfc-editor.org/rfc/rfc4648#section-5) URL and filename safe base 64 alphabet.
+    pub const BASE64_URL: Self = Self(b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_");
+    /// The lowercase hexadecimal digits.
+    pub const HEX: Self = Self(b"0123456789abcdef");
+}
+
+impl<'a> Charset<'a> {
+    /// Create a custom [Charset](struct.Charset.html) from an alphabet of ASCII characters.
+    ///
+    /// Returns [`None`] if the alphabet is empty or contains a byte that is not ASCII, because the generated
+    /// strings must be valid UTF-8. Use [`Acorn::fill_chars`] for alphabets of any [`char`].
+    ///
+    /// [`None`]: https://doc.rust-lang.org/core/option/enum.Option.html#variant.None
+    /// [`char`]: https://doc.rust-lang.org/core/primitive.char.html
+    /// [`Acorn::fill_chars`]: struct.Acorn.html#method.fill_chars
+    #[must_use]
+    pub const fn new(alphabet: &'a [u8]) -> Option<Self> {
+        if alphabet.is_empty() || !alphabet.is_ascii() {None} else {Some(Self(alphabet))}
+    }
+    /// Get the characters of the alphabet.
+    #[must_use]
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.0
+    }
+}
+
+impl Acorn {
+    /// Fill a buffer with random characters from a [Charset](struct.Charset.html) and return them as a [`str`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Charset, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let mut buffer = [0; 8];
+    /// let token = prng.fill_string(&mut buffer, Charset::BASE58);
+    ///
+    /// assert_eq!("Lnc1sPye", token); // assuming above input. further calls will produce different results
+    /// ```
+    /// Note that the output is not a secret, see [Charset](struct.Charset.html). As many characters as fit into the
+    /// highest half of a raw output are drawn at once, so even the last of them does not repeat with a short period.
+    ///
+    /// [`str`]: https://doc.rust-lang.org/core/primitive.str.html
+    pub const fn fill_string<'a>(&mut self, buffer: &'a mut [u8], charset: Charset) -> &'a str {
+        self.fill_from_alphabet(buffer, charset.0);
+        match core::str::from_utf8(buffer) {
+            Ok(string) => string,
+            Err(_) => unreachable!(),
+        }
+    }
+    /// Generate an array of `N` random characters from a [Charset](struct.Charset.html), see [`fill_string`].
+    ///
+    /// [`fill_string`]: struct.Acorn.html#method.fill_string
+    #[must_use]
+    pub const fn generate_string_array<const N: usize>(&mut self, charset: Charset) -> [u8; N] {
+        let mut string = [0; N];
+        self.fill_from_alphabet(&mut string, charset.0);
+        string
+    }
+    /// Fill a buffer with random characters from an alphabet of any [`char`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let mut suits = [' '; 5];
+    /// prng.fill_chars(&mut suits, &['♠', '♥', '♦', '♣']);
+    ///
+    /// assert!(suits.iter().all(|suit| "♠♥♦♣".contains(*suit)));
+    /// ```
+    /// Note that an empty alphabet leaves the buffer unchanged.
+    ///
+    /// [`char`]: https://doc.rust-lang.org/core/primitive.char.html
+    #[allow(clippy::cast_possible_truncation)]
+    pub const fn fill_chars(&mut self, buffer: &mut [char], alphabet: &[char]) {
+        if alphabet.is_empty() {return;}
+        let base = alphabet.len() as u128;
+        let mut index = 0;
+        while index < buffer.len() {
+            let (mut number, count) = self.generate_indices(base, buffer.len() - index);
+            let mut position = index + count;
+            while position > index {
+                position -= 1;
+                buffer[position] = alphabet[(number % base) as usize];
+                number /= base;
+            }
+            index += count;
+        }
+    }
+    /// Generate a [`String`] of `length` random characters from a [Charset](struct.Charset.html).
+    ///
+    /// This is identical to [`fill_string`] with a buffer of the same length and requires the `alloc` feature.
+    ///
+    /// [`String`]: https://doc.rust-lang.org/alloc/string/struct.String.html
+    /// [`fill_string`]: struct.Acorn.html#method.fill_string
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn generate_string(&mut self, length: usize, charset: Charset) -> alloc::string::String {
+        let mut string = alloc::vec![0; length];
+        alloc::string::String::from(self.fill_string(&mut string, charset))
+    }
+    /// Generate a [`String`] of `length` random characters from an alphabet of any [`char`].
+    ///
+    /// This is identical to [`fill_chars`] with a buffer of the same length and requires the `alloc` feature. An
+    /// empty alphabet produces an empty [`String`].
+    ///
+    /// [`String`]: https://doc.rust-lang.org/alloc/string/struct.String.html
+    /// [`char`]: https://doc.rust-lang.org/core/primitive.char.html
+    /// [`fill_chars`]: struct.Acorn.html#method.fill_chars
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn generate_string_from_chars(&mut self, length: usize, alphabet: &[char]) -> alloc::string::String {
+        if alphabet.is_empty() {return alloc::string::String::new();}
+        let mut string = alloc::vec![' '; length];
+        self.fill_chars(&mut string, alphabet);
+        string.into_iter().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prng;
+    #[test]
+    fn builtin_charsets() {
+        assert_eq!(Charset::ALPHANUMERIC.as_bytes().len(), 62);
+        assert_eq!(Charset::BASE32.as_bytes().len(), 32);
+        assert_eq!(Charset::BASE58.as_bytes().len(), 58);
+        assert_eq!(Charset::BASE64_URL.as_bytes().len(), 64);
+        assert_eq!(Charset::HEX.as_bytes().len(), 16);
+        for charset in [Charset::ALPHANUMERIC, Charset::BASE32, Charset::BASE58, Charset::BASE64_URL, Charset::HEX] {
+            let bytes = charset.as_bytes();
+            assert!((1..bytes.len()).all(|index| !bytes[..index].contains(&bytes[index]))); // no duplicates
+            assert_eq!(Charset::new(bytes), Some(charset));
+        }
+    }
+    #[test]
+    fn custom_charsets() {
+        assert_eq!(Charset::new(b""), None);
+        assert_eq!(Charset::new("ä".as_bytes()), None);
+        let mut prng = prng();
+        let mut counts = [0_u32; 3];
+        for byte in prng.generate_string_array::<3000>(Charset::new(b"xyz").unwrap()) {
+            counts[usize::from(byte - b'x')] += 1;
+        }
+        assert!(counts.iter().all(|count| (900..1100).contains(count)));
+        assert_eq!(prng.generate_string_array::<4>(Charset::new(b"a").unwrap()), *b"aaaa");
+    }
+    #[test]
+    fn fill_matches_array() {
+        let mut buffer = [0; 40];
+        assert_eq!(prng().fill_string(&mut buffer, Charset::BASE64_URL).as_bytes(),
+            prng().generate_string_array::<40>(Charset::BASE64_URL));
+        assert_eq!(prng().fill_string(&mut [], Charset::HEX), "");
+    }
+    #[test]
+    fn chars_from_any_alphabet() {
+        let alphabet = ['α', 'β', 'γ', '😀'];
+        let mut buffer = ['?'; 200];
+        let mut prng = prng();
+        prng.fill_chars(&mut buffer, &alphabet);
+        assert!(alphabet.iter().all(|c| buffer.contains(c)));
+        assert!(buffer.iter().all(|c| alphabet.contains(c)));
+        prng.fill_chars(&mut buffer, &[]);
+        assert!(buffer.iter().all(|c| alphabet.contains(c)));
+    }
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn alloc_strings() {
+        let mut buffer = [0; 16];
+        assert_eq!(prng().generate_string(16, Charset::BASE32), prng().fill_string(&mut buffer, Charset::BASE32));
+        assert_eq!(prng().generate_string_from_chars(3, &['é']), "ééé");
+        assert_eq!(prng().generate_string_from_chars(3, &[]), "");
+    }
+}
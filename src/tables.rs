@@ -0,0 +1,148 @@
+/// Generate a deterministic array of random numbers in a `const` or `static` item.
+///
+/// Every method of [Acorn](struct.Acorn.html) is a `const fn`, so this expands to a loop over a generator created
+/// with [`Acorn::with_version`] that the compiler evaluates while building. The table is baked into the binary, for
+/// example Zobrist hashing keys, dither matrices or shuffled S-box-like permutations. The same invocation evaluated
+/// at runtime produces exactly the same array.
+///
+/// The generator always uses [`Version::V4`] because narrow ranges drawn from the original stream take the lowest
+/// bits of each output, whose short periods would make small tables nearly identical for every seed.
+///
+/// The element type is one of [`u8`], [`u16`], [`u32`], [`u64`], [`u128`] or [`usize`] and the forms are:
+/// - `acorn_const_array!(order, seed, [T; N])` draws every element over the full range of `T`, like
+///   [`Acorn::generate`].
+/// - `acorn_const_array!(order, seed, [T; N], range)` draws every element within an inclusive range, like the
+///   `generate_*_between_range` methods.
+/// - `acorn_const_array!(order, seed, shuffled [T; N])` is a uniformly shuffled permutation of `0..N`, where `N`
+///   must not exceed the number of values of `T` or the invocation fails to compile.
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::{acorn_const_array, Order, Seed};
+///
+/// const ZOBRIST: [u64; 768] = acorn_const_array!(Order::new(45), Seed::new(1_000_000), [u64; 768]);
+/// static DITHER: [u8; 64] = acorn_const_array!(Order::new(45), Seed::new(7), [u8; 64], 0..=63);
+/// const SBOX: [u8; 256] = acorn_const_array!(Order::new(45), Seed::new(42), shuffled [u8; 256]);
+///
+/// assert!(DITHER.iter().all(|level| *level < 64));
+/// assert!((0..=255).all(|byte| SBOX.contains(&byte)));
+/// assert_eq!(ZOBRIST, acorn_const_array!(Order::new(45), Seed::new(1_000_000), [u64; 768])); // at runtime
+/// ```
+///
+/// ```compile_fail
+/// use acorn_prng::{acorn_const_array, Order, Seed};
+///
+/// const SBOX: [u8; 257] = acorn_const_array!(Order::new(45), Seed::new(42), shuffled [u8; 257]); // 256 is not a u8
+/// ```
+///
+/// # Compile time
+///
+/// Each raw output takes one pass over the [Order](struct.Order.html), so evaluating a table costs roughly
+/// `order * (20 + outputs)` loop iterations, where 20 is the warm-up and there is at least one output per element,
+/// more for [`u128`] elements or ranges wider than the modulus and slightly more for rejected draws. Constant
+/// evaluation is interpreted and far slower than running the same code, so keep the order small for large tables.
+/// If evaluation runs for very long the compiler reports the deny-by-default `long_running_const_eval` lint,
+/// which can be allowed on the item that uses the table once the build time is acceptable.
+///
+/// [`u8`]: https://doc.rust-lang.org/core/primitive.u8.html
+/// [`u16`]: https://doc.rust-lang.org/core/primitive.u16.html
+/// [`u32`]: https://doc.rust-lang.org/core/primitive.u32.html
+/// [`u64`]: https://doc.rust-lang.org/core/primitive.u64.html
+/// [`u128`]: https://doc.rust-lang.org/core/primitive.u128.html
+/// [`usize`]: https://doc.rust-lang.org/core/primitive.usize.html
+/// [`Acorn::with_version`]: struct.Acorn.html#method.with_version
+/// [`Version::V4`]: enum.Version.html#variant.V4
+/// [`Acorn::generate`]: struct.Acorn.html#method.generate
+#[macro_export]
+macro_rules! acorn_const_array {
+    ($order:expr, $seed:expr, shuffled [$t:ident; $n:expr] $(,)?) => {{
+        let mut prng = $crate::Acorn::with_version($order, $seed, $crate::Version::V4);
+        const {assert!($n == 0 || $n as u128 - 1 <= <$t>::MAX as u128,
+            "the permutation is longer than the number of values of the element type")};
+        let mut array: [$t; $n] = [0; $n];
+        let mut value: $t = 0;
+        let mut index = 0;
+        while index < $n {
+            array[index] = value;
+            value = value.wrapping_add(1);
+            index += 1;
+        }
+        while index > 1 { // Fisher-Yates from the end
+            index -= 1;
+            let other = prng.generate_usize_between_range(0..=index);
+            array.swap(index, other);
+        }
+        array
+    }};
+    ($order:expr, $seed:expr, [$t:ident; $n:expr], $range:expr $(,)?) => {{
+        let mut prng = $crate::Acorn::with_version($order, $seed, $crate::Version::V4);
+        let range: ::core::ops::RangeInclusive<$t> = $range;
+        let (start, end) = (*range.start(), *range.end());
+        let mut array: [$t; $n] = [0; $n];
+        let mut index = 0;
+        while index < $n {
+            array[index] = $crate::acorn_const_array!(@between $t, prng, start..=end);
+            index += 1;
+        }
+        array
+    }};
+    ($order:expr, $seed:expr, [$t:ident; $n:expr] $(,)?) => {{
+        let mut prng = $crate::Acorn::with_version($order, $seed, $crate::Version::V4);
+        let mut array: [$t; $n] = [0; $n];
+        let mut index = 0;
+        while index < $n {
+            #[allow(clippy::cast_possible_truncation)]
+            let value = $crate::acorn_const_array!(@full $t, prng);
+            array[index] = value;
+            index += 1;
+        }
+        array
+    }};
+    (@between u8, $prng:ident, $range:expr) => {$prng.generate_u8_between_range($range)};
+    (@between u16, $prng:ident, $range:expr) => {$prng.generate_u16_between_range($range)};
+    (@between u32, $prng:ident, $range:expr) => {$prng.generate_u32_between_range($range)};
+    (@between u64, $prng:ident, $range:expr) => {$prng.generate_u64_between_range($range)};
+    (@between u128, $prng:ident, $range:expr) => {$prng.generate_u128_between_range($range)};
+    (@between usize, $prng:ident, $range:expr) => {$prng.generate_usize_between_range($range)};
+    (@full u128, $prng:ident) => {$prng.generate_bits(128)};
+    (@full $t:ident, $prng:ident) => {$prng.generate_bits(<$t>::BITS) as $t};
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Acorn, Order, Seed, Version};
+    const FULL: [u32; 256] = acorn_const_array!(Order::new(45), Seed::new(1_000_000), [u32; 256]);
+    const WIDE: [u128; 16] = acorn_const_array!(Order::new(50), Seed::new(3), [u128; 16]);
+    const RANGED: [u16; 100] = acorn_const_array!(Order::new(45), Seed::new(1_000_000), [u16; 100], 1_000..=1_999);
+    const SIZES: [usize; 10] = acorn_const_array!(Order::new(46), Seed::new(9), [usize; 10], 0..=usize::MAX);
+    const SHUFFLED: [u8; 256] = acorn_const_array!(Order::new(45), Seed::new(1_000_000), shuffled [u8; 256]);
+    #[test]
+    fn const_matches_runtime() {
+        let mut prng = Acorn::with_version(Order::new(45), Seed::new(1_000_000), Version::V4);
+        assert!(FULL.iter().all(|number| *number == prng.generate::<u32>()));
+        let mut prng = Acorn::with_version(Order::new(50), Seed::new(3), Version::V4);
+        assert!(WIDE.iter().all(|number| *number == prng.generate::<u128>()));
+        let mut prng = Acorn::with_version(Order::new(45), Seed::new(1_000_000), Version::V4);
+        assert!(RANGED.iter().all(|number| *number == prng.generate_u16_between_range(1_000..=1_999)));
+        let mut prng = Acorn::with_version(Order::new(46), Seed::new(9), Version::V4);
+        assert!(SIZES.iter().all(|number| *number == prng.generate_usize_between_range(0..=usize::MAX)));
+        assert_eq!(SHUFFLED, acorn_const_array!(Order::new(45), Seed::new(1_000_000), shuffled [u8; 256]));
+    }
+    #[test]
+    fn shuffled_permutations() {
+        let mut sorted = SHUFFLED;
+        sorted.sort_unstable();
+        assert!(sorted.iter().enumerate().all(|(index, byte)| usize::from(*byte) == index));
+        assert_ne!(SHUFFLED, sorted);
+        let mut counts = [[0_u32; 4]; 4];
+        for seed in 0..4_000 {
+            let permutation = acorn_const_array!(Order::new(45), Seed::new(1_000_000 + 2 * seed), shuffled [u8; 4]);
+            for (position, value) in permutation.iter().enumerate() {
+                counts[position][usize::from(*value)] += 1;
+            }
+        }
+        assert!(counts.iter().flatten().all(|count| (850..1150).contains(count)));
+        assert_eq!(acorn_const_array!(Order::new(45), Seed::new(1), shuffled [u64; 1]), [0]);
+    }
+}
@@ -0,0 +1,205 @@
+use core::cell::RefCell;
+use core::marker::PhantomData;
+use core::ops::RangeInclusive;
+use core::sync::atomic::{AtomicU64, Ordering};
+
+use crate::{Acorn, AcornUniform, Order, Seed, Version};
+
+std::thread_local! {
+    static THREAD_ACORN: RefCell<Option<(Acorn, Seed)>> = const {RefCell::new(None)};
+}
+
+/// The number of unnamed threads that have seeded their generator so far.
+static UNNAMED_THREADS: AtomicU64 = AtomicU64::new(0);
+
+/// The name of the environment variable that fixes the seeds of every [`thread_acorn`](fn.thread_acorn.html).
+pub const SEED_VARIABLE: &str = "ACORN_SEED";
+
+/// A handle to the lazily seeded [Acorn](struct.Acorn.html) generator of the current thread, see
+/// [`thread_acorn`](fn.thread_acorn.html).
+///
+/// The handle cannot be sent to another thread, where it would refer to a different generator.
+#[derive(Clone, Copy, Debug)]
+pub struct ThreadAcorn(PhantomData<*const ()>);
+
+/// Get a handle to the generator of the current thread, which requires the `std` feature.
+///
+/// Each thread has its own generator, equivalent to [`Acorn::with_version`] with an [Order](struct.Order.html) of 45
+/// and [`Version::V4`]. It is seeded on first use:
+/// - from the `ACORN_SEED` environment variable if it is set, as a decimal number or, for any other text, as
+///   [`Seed::from_str_hash`] of it, combined with the name of the thread so that threads draw independent streams;
+/// - otherwise from [`Seed::from_entropy`] on unix targets, falling back to [`Seed::from_system_time`].
+///
+/// The seed of a thread named `name` is [`Seed::from_str_hash`] of `"{seed}/{name}"`, where `seed` is the decimal
+/// value of the variable's seed. Unnamed threads are numbered `#0`, `#1`, … in the order they first use their
+/// generator. The test harness names each test's thread after the test, so with `ACORN_SEED` set every test draws
+/// the same numbers on each run, yet tests running in parallel do not draw correlated values.
+///
+/// Print [`ThreadAcorn::seed`], which is the seed of the thread rather than the variable, when a test fails and
+/// pass it to [`ThreadAcorn::reseed`], or run the test again with the same `ACORN_SEED`, to reproduce the run.
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::{thread_acorn, Seed};
+///
+/// let prng = thread_acorn();
+/// prng.reseed(Seed::new(1_000_000));
+/// let die = prng.generate_range(1_u8..=6);
+/// let seed = prng.seed();
+///
+/// assert_eq!(seed.get(), 1_000_000);
+/// assert!((1..=6).contains(&die));
+/// ```
+///
+/// [`Acorn::with_version`]: struct.Acorn.html#method.with_version
+/// [`Version::V4`]: enum.Version.html#variant.V4
+/// [`Seed::from_str_hash`]: struct.Seed.html#method.from_str_hash
+/// [`Seed::from_entropy`]: struct.Seed.html#method.from_entropy
+/// [`Seed::from_system_time`]: struct.Seed.html#method.from_system_time
+/// [`ThreadAcorn::seed`]: struct.ThreadAcorn.html#method.seed
+/// [`ThreadAcorn::reseed`]: struct.ThreadAcorn.html#method.reseed
+#[must_use]
+pub fn thread_acorn() -> ThreadAcorn {
+    ThreadAcorn(PhantomData)
+}
+
+/// Generate a random value of any type implementing [`AcornUniform`] with the generator of the current thread,
+/// which requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// let number = acorn_prng::random::<u32>();
+/// let coin: bool = acorn_prng::random();
+/// ```
+///
+/// [`AcornUniform`]: trait.AcornUniform.html
+#[must_use]
+pub fn random<T: AcornUniform>() -> T {
+    thread_acorn().generate()
+}
+
+/// Choose the initial seed of a thread from the value of the environment variable, if it is set, and the name of
+/// the thread.
+fn initial_seed(variable: Option<&str>, thread: &str) -> Seed {
+    match variable {
+        Some(text) => {
+            let seed = text.trim().parse().map_or_else(|_| Seed::from_str_hash(text), Seed::new_unclamped);
+            Seed::from_str_hash(&std::format!("{}/{thread}", seed.get()))
+        },
+        #[cfg(unix)]
+        None => Seed::from_entropy().unwrap_or_else(|_| Seed::from_system_time()),
+        #[cfg(not(unix))]
+        None => Seed::from_system_time(),
+    }
+}
+
+/// Borrow the generator of the current thread and its seed, seeding it on first use.
+fn state<R>(f: impl FnOnce(&mut (Acorn, Seed)) -> R) -> R {
+    THREAD_ACORN.with(|cell| f(cell.borrow_mut().get_or_insert_with(|| {
+        let thread = std::thread::current().name().map_or_else(
+            || std::format!("#{}", UNNAMED_THREADS.fetch_add(1, Ordering::Relaxed)),
+            std::borrow::ToOwned::to_owned,
+        );
+        let seed = initial_seed(std::env::var(SEED_VARIABLE).ok().as_deref(), &thread);
+        (seeded(seed), seed)
+    })))
+}
+
+const fn seeded(seed: Seed) -> Acorn {
+    Acorn::with_version(Order::new(45), seed, Version::V4)
+}
+
+impl ThreadAcorn {
+    /// Call a function with exclusive access to the generator of the current thread.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the function uses the generator of the current thread again, for example through [`random`].
+    ///
+    /// [`random`]: fn.random.html
+    pub fn with<R>(self, f: impl FnOnce(&mut Acorn) -> R) -> R {
+        state(|(prng, _)| f(prng))
+    }
+    /// The seed of the generator of the current thread, choosing it now if it has not been used yet.
+    ///
+    /// With `ACORN_SEED` set this is the seed derived for this thread, see [`thread_acorn`](fn.thread_acorn.html).
+    #[must_use]
+    pub fn seed(self) -> Seed {
+        state(|(_, seed)| *seed)
+    }
+    /// Replace the generator of the current thread with a new one seeded with `seed`, to reproduce a run in tests.
+    pub fn reseed(self, seed: Seed) {
+        THREAD_ACORN.with(|cell| *cell.borrow_mut() = Some((seeded(seed), seed)));
+    }
+    /// Generate a random value of any type implementing [`AcornUniform`], see [`Acorn::generate`].
+    ///
+    /// [`AcornUniform`]: trait.AcornUniform.html
+    /// [`Acorn::generate`]: struct.Acorn.html#method.generate
+    #[must_use]
+    pub fn generate<T: AcornUniform>(self) -> T {
+        self.with(Acorn::generate)
+    }
+    /// Generate a random value of any type implementing [`AcornUniform`] within a given [`RangeInclusive`], see
+    /// [`Acorn::generate_range`].
+    ///
+    /// [`AcornUniform`]: trait.AcornUniform.html
+    /// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+    /// [`Acorn::generate_range`]: struct.Acorn.html#method.generate_range
+    #[must_use]
+    pub fn generate_range<T: AcornUniform>(self, range: RangeInclusive<T>) -> T {
+        self.with(|prng| prng.generate_range(range))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[test]
+    fn seed_from_variable() {
+        assert_eq!(initial_seed(Some("42"), "main"), Seed::from_str_hash("42/main"));
+        assert_eq!(initial_seed(Some(" 1000000\n"), "#0"), Seed::from_str_hash("1000000/#0"));
+        let hashed = Seed::from_str_hash("nightly-7").get();
+        assert_eq!(initial_seed(Some("nightly-7"), "main"), Seed::from_str_hash(&std::format!("{hashed}/main")));
+    }
+    #[test]
+    fn threads_derive_distinct_seeds() {
+        let first = initial_seed(Some("42"), "tests::first");
+        assert_eq!(initial_seed(Some("42"), "tests::first"), first); // reproducible by name
+        assert_ne!(initial_seed(Some("42"), "tests::second"), first);
+        assert_ne!(initial_seed(Some("43"), "tests::first"), first);
+        let other = std::thread::spawn(|| (thread_acorn().seed(), random::<u64>())).join().unwrap();
+        assert_eq!(other.1, seeded(other.0).generate());
+    }
+    #[test]
+    fn reseed_reproduces_runs() {
+        let prng = thread_acorn();
+        let seed = prng.seed();
+        assert_eq!(prng.seed(), seed); // the seed is chosen once
+        let first: [u64; 4] = core::array::from_fn(|_| random());
+        prng.reseed(seed);
+        let mut expected = seeded(seed);
+        assert_eq!(first, core::array::from_fn(|_| expected.generate::<u64>()));
+        prng.reseed(Seed::new_unclamped(7));
+        assert_eq!(prng.seed().get(), 7);
+        assert_eq!(prng.generate_range(0_u32..=999), seeded(Seed::new_unclamped(7)).generate_range(0..=999));
+        assert_eq!(prng.with(|prng| prng.position()), 1);
+    }
+    #[test]
+    fn threads_are_independent() {
+        thread_acorn().reseed(Seed::new(1_000_000));
+        let other = std::thread::spawn(|| {
+            thread_acorn().reseed(Seed::new(1_000_000));
+            random::<u128>()
+        }).join().unwrap();
+        assert_eq!(random::<u128>(), other);
+        assert_ne!(thread_acorn().with(|prng| prng.position()), 0);
+        assert_eq!(std::thread::spawn(|| thread_acorn().with(|prng| prng.position())).join().unwrap(), 0);
+    }
+    #[test]
+    #[should_panic(expected = "already borrowed")]
+    fn nested_use_panics() {
+        thread_acorn().with(|_| random::<u8>());
+    }
+}
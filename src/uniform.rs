@@ -0,0 +1,384 @@
+use core::ops::RangeInclusive;
+
+use crate::Acorn;
+
+/// Types that can be generated uniformly by an [Acorn](struct.Acorn.html) generator.
+///
+/// This is implemented for every primitive integer, [`f32`], [`f64`], [`bool`] and [`char`]. Implement it for your
+/// own types to use them with [`Acorn::generate`] and [`Acorn::generate_range`].
+///
+/// # Examples
+///
+/// ```
+/// use acorn_prng::{Acorn, AcornUniform, Order, Seed};
+///
+/// #[derive(Debug, PartialEq, PartialOrd)]
+/// struct Die(u8);
+///
+/// impl AcornUniform for Die {
+///     fn generate(prng: &mut Acorn) -> Self {
+///         Die(prng.generate_range(1..=6))
+///     }
+///     fn generate_range(prng: &mut Acorn, range: core::ops::RangeInclusive<Self>) -> Self {
+///         Die(prng.generate_range(range.start().0..=range.end().0))
+///     }
+/// }
+///
+/// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+/// let die: Die = prng.generate();
+///
+/// assert!((1..=6).contains(&die.0));
+/// ```
+///
+/// [`f32`]: https://doc.rust-lang.org/core/primitive.f32.html
+/// [`f64`]: https://doc.rust-lang.org/core/primitive.f64.html
+/// [`bool`]: https://doc.rust-lang.org/core/primitive.bool.html
+/// [`char`]: https://doc.rust-lang.org/core/primitive.char.html
+/// [`Acorn::generate`]: struct.Acorn.html#method.generate
+/// [`Acorn::generate_range`]: struct.Acorn.html#method.generate_range
+pub trait AcornUniform: Sized {
+    /// Generate a value uniformly over every possible value of the type.
+    ///
+    /// Floating point numbers are generated between 0 (inclusive) and 1 (exclusive).
+    fn generate(prng: &mut Acorn) -> Self;
+    /// Generate a value uniformly within a given [`RangeInclusive`].
+    ///
+    /// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+    fn generate_range(prng: &mut Acorn, range: RangeInclusive<Self>) -> Self;
+}
+
+/// Integer types that can be generated with a fixed number of decimal digits or bits by an
+/// [Acorn](struct.Acorn.html) generator.
+///
+/// Negative numbers have the same number of digits and bits as their absolute value.
+pub trait AcornFixedLength: Sized {
+    /// Generate a value with exactly `length` decimal digits.
+    ///
+    /// Note that the `length` is clamped between 1 and the number of digits of the largest value of the type.
+    fn generate_fixed_length(prng: &mut Acorn, length: usize) -> Self;
+    /// Generate a value with exactly `bits` bits, that is with the highest of `bits` bits set.
+    ///
+    /// Note that `bits` is clamped between 1 and the number of bits of the type.
+    fn generate_fixed_bit_length(prng: &mut Acorn, bits: u32) -> Self;
+}
+
+impl Acorn {
+    /// Generate a random value of any type implementing [`AcornUniform`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number: i16 = prng.generate();
+    /// let coin = prng.generate::<bool>();
+    /// let fraction = prng.generate::<f64>();
+    ///
+    /// assert!((0.0..1.0).contains(&fraction));
+    /// ```
+    ///
+    /// [`AcornUniform`]: trait.AcornUniform.html
+    pub fn generate<T: AcornUniform>(&mut self) -> T {
+        T::generate(self)
+    }
+    /// Generate a random value of any type implementing [`AcornUniform`] within a given [`RangeInclusive`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_range(71_u16..=777);
+    ///
+    /// assert_eq!(571, number); // identical to generate_u16_between_range
+    /// assert!((-5..=5).contains(&prng.generate_range(-5_i8..=5)));
+    /// assert!(('a'..='z').contains(&prng.generate_range('a'..='z')));
+    /// ```
+    ///
+    /// [`AcornUniform`]: trait.AcornUniform.html
+    /// [`RangeInclusive`]: https://doc.rust-lang.org/core/ops/struct.RangeInclusive.html
+    pub fn generate_range<T: AcornUniform>(&mut self, range: RangeInclusive<T>) -> T {
+        T::generate_range(self, range)
+    }
+    /// Generate a random integer of any type implementing [`AcornFixedLength`] with a fixed digit length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_fixed_length::<u32>(3);
+    ///
+    /// assert_eq!(822, number); // identical to generate_fixed_length_u32
+    /// assert_eq!(prng.generate_fixed_length::<i64>(4).unsigned_abs().to_string().len(), 4);
+    /// ```
+    ///
+    /// [`AcornFixedLength`]: trait.AcornFixedLength.html
+    pub fn generate_fixed_length<T: AcornFixedLength>(&mut self, length: usize) -> T {
+        T::generate_fixed_length(self, length)
+    }
+    /// Generate a random integer of any type implementing [`AcornFixedLength`] with a fixed bit length.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use acorn_prng::{Acorn, Order, Seed};
+    ///
+    /// let mut prng = Acorn::new(Order::new(45), Seed::new(1_000_000));
+    /// let number = prng.generate_fixed_bit_length::<u32>(8);
+    ///
+    /// assert_eq!(210, number); // identical to generate_fixed_bit_length_u32
+    /// assert_eq!(prng.generate_fixed_bit_length::<i64>(12).unsigned_abs().ilog2(), 11);
+    /// ```
+    ///
+    /// [`AcornFixedLength`]: trait.AcornFixedLength.html
+    pub fn generate_fixed_bit_length<T: AcornFixedLength>(&mut self, bits: u32) -> T {
+        T::generate_fixed_bit_length(self, bits)
+    }
+}
+
+macro_rules! impl_unsigned {
+    ($($t:ty => $between_range:ident, $fixed_length:ident, $fixed_bit_length:ident;)*) => {$(
+        impl AcornUniform for $t {
+            #[allow(clippy::cast_possible_truncation)]
+            fn generate(prng: &mut Acorn) -> Self {
+                prng.generate_bits(<$t>::BITS) as $t
+            }
+            /// Note that this panics if the start of the range is greater than the end.
+            fn generate_range(prng: &mut Acorn, range: RangeInclusive<Self>) -> Self {
+                assert!(range.start() <= range.end(), "invalid integer range");
+                prng.$between_range(range)
+            }
+        }
+        impl AcornFixedLength for $t {
+            fn generate_fixed_length(prng: &mut Acorn, length: usize) -> Self {
+                prng.$fixed_length(length)
+            }
+            fn generate_fixed_bit_length(prng: &mut Acorn, bits: u32) -> Self {
+                prng.$fixed_bit_length(bits)
+            }
+        }
+    )*};
+}
+
+impl_unsigned! {
+    u8 => generate_u8_between_range, generate_fixed_length_u8, generate_fixed_bit_length_u8;
+    u16 => generate_u16_between_range, generate_fixed_length_u16, generate_fixed_bit_length_u16;
+    u32 => generate_u32_between_range, generate_fixed_length_u32, generate_fixed_bit_length_u32;
+    u64 => generate_u64_between_range, generate_fixed_length_u64, generate_fixed_bit_length_u64;
+    u128 => generate_u128_between_range, generate_fixed_length_u128, generate_fixed_bit_length_u128;
+    usize => generate_usize_between_range, generate_fixed_length_usize, generate_fixed_bit_length_usize;
+}
+
+macro_rules! impl_signed {
+    ($($t:ty => $fixed_bit_length:ident;)*) => {$(
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_possible_wrap, clippy::cast_lossless)]
+        impl AcornUniform for $t {
+            fn generate(prng: &mut Acorn) -> Self {
+                prng.generate_bits(<$t>::BITS) as $t
+            }
+            /// Note that this panics if the start of the range is greater than the end.
+            fn generate_range(prng: &mut Acorn, range: RangeInclusive<Self>) -> Self {
+                assert!(range.start() <= range.end(), "invalid integer range");
+                let span = (*range.end() as i128).wrapping_sub(*range.start() as i128) as u128;
+                let offset = if span == u128::MAX { // every i128, which no range of offsets below 2^128 can cover
+                    prng.generate_bits(128)
+                } else {
+                    prng.generate_number_between_range(0..=span)
+                };
+                (*range.start() as i128).wrapping_add(offset as i128) as $t
+            }
+        }
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_possible_wrap, clippy::cast_lossless)]
+        impl AcornFixedLength for $t {
+            fn generate_fixed_length(prng: &mut Acorn, length: usize) -> Self {
+                let max_length = <$t>::MAX.ilog10() as usize + 1;
+                let (negative, magnitude) = prng.generate_fixed_length_signed(length, max_length, <$t>::MAX as u128);
+                if negative {(magnitude as i128).wrapping_neg() as $t} else {magnitude as $t}
+            }
+            fn generate_fixed_bit_length(prng: &mut Acorn, bits: u32) -> Self {
+                prng.$fixed_bit_length(bits)
+            }
+        }
+    )*};
+}
+
+impl_signed! {
+    i8 => generate_fixed_bit_length_i8;
+    i16 => generate_fixed_bit_length_i16;
+    i32 => generate_fixed_bit_length_i32;
+    i64 => generate_fixed_bit_length_i64;
+    i128 => generate_fixed_bit_length_i128;
+    isize => generate_fixed_bit_length_isize;
+}
+
+macro_rules! impl_float {
+    ($($t:ty => $bits:literal;)*) => {$(
+        impl AcornUniform for $t {
+            #[allow(clippy::cast_precision_loss)]
+            fn generate(prng: &mut Acorn) -> Self {
+                prng.generate_bits($bits) as $t / (1_u128 << $bits) as $t
+            }
+            /// Note that this panics if either end of the range is not finite or the start is greater than the end.
+            #[allow(clippy::cast_precision_loss)]
+            fn generate_range(prng: &mut Acorn, range: RangeInclusive<Self>) -> Self {
+                let (start, end) = (*range.start(), *range.end());
+                assert!(start.is_finite() && end.is_finite() && start <= end, "invalid floating point range");
+                let fraction = prng.generate_bits($bits) as $t / ((1_u128 << $bits) - 1) as $t; // inclusive of 1
+                let number = if (end - start).is_finite() {
+                    start + (end - start) * fraction
+                } else {
+                    start * (1.0 - fraction) + end * fraction
+                };
+                number.clamp(start, end)
+            }
+        }
+    )*};
+}
+
+impl_float! {
+    f32 => 24;
+    f64 => 53;
+}
+
+impl AcornUniform for bool {
+    fn generate(prng: &mut Acorn) -> Self {
+        prng.generate_bits(1) == 1
+    }
+    /// Note that this panics if the start of the range is `true` and the end is `false`.
+    fn generate_range(prng: &mut Acorn, range: RangeInclusive<Self>) -> Self {
+        assert!(range.start() <= range.end(), "invalid bool range");
+        if range.start() == range.end() {*range.start()} else {prng.generate()}
+    }
+}
+
+/// The number of surrogate code points that are not valid [`char`]s.
+const SURROGATES: u32 = 0xE000 - 0xD800;
+
+/// Map a [`char`] onto a contiguous index without the surrogate gap.
+const fn char_to_index(c: char) -> u32 {
+    if (c as u32) < 0xD800 {c as u32} else {c as u32 - SURROGATES}
+}
+
+impl AcornUniform for char {
+    fn generate(prng: &mut Acorn) -> Self {
+        prng.generate_range('\0'..=char::MAX)
+    }
+    /// Note that this panics if the start of the range is greater than the end.
+    #[allow(clippy::cast_possible_truncation)]
+    fn generate_range(prng: &mut Acorn, range: RangeInclusive<Self>) -> Self {
+        assert!(range.start() <= range.end(), "invalid char range");
+        let (start, end) = (char_to_index(*range.start()), char_to_index(*range.end()));
+        let index = prng.generate_number_between_range(u128::from(start)..=u128::from(end)) as u32;
+        let code_point = if index < 0xD800 {index} else {index + SURROGATES};
+        char::from_u32(code_point).unwrap_or(char::REPLACEMENT_CHARACTER) // every index maps to a valid char
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prng;
+    #[test]
+    fn wrappers_match_existing_methods() {
+        assert_eq!(prng().generate_range(71_u8..=255), prng().generate_u8_between_range(71..=255));
+        assert_eq!(prng().generate_range(71_usize..=777), prng().generate_usize_between_range(71..=777));
+        assert_eq!(prng().generate_range(71_u128..=777), prng().generate_u128_between_range(71..=777));
+        assert_eq!(prng().generate_fixed_length::<u8>(3), prng().generate_fixed_length_u8(3));
+        assert_eq!(prng().generate_fixed_bit_length::<u16>(9), prng().generate_fixed_bit_length_u16(9));
+        assert_eq!(prng().generate_fixed_bit_length::<i64>(40), prng().generate_fixed_bit_length_i64(40));
+        assert_eq!(prng().generate_fixed_length::<u64>(20), prng().generate_fixed_length_u64(20));
+    }
+    #[test]
+    fn full_range_integers() {
+        let mut prng = prng();
+        assert_eq!(prng.generate::<u8>(), 79); // the highest 8 bits of the first raw output
+        let (mut low, mut high, mut negative) = (false, false, false);
+        for _ in 0..1000 {
+            let number = prng.generate::<u128>();
+            low |= number < 2_u128.pow(120);
+            high |= number >= 2_u128.pow(127);
+            negative |= prng.generate::<i8>() < 0;
+        }
+        assert!(low && high && negative);
+    }
+    #[test]
+    fn signed_ranges() {
+        let mut prng = prng();
+        let mut seen = [false; 11];
+        for _ in 0..1000 {
+            let number = prng.generate_range(-5_i32..=5);
+            assert!((-5..=5).contains(&number));
+            seen[(number + 5).unsigned_abs() as usize] = true;
+        }
+        assert!(seen.iter().all(|seen| *seen));
+        assert_eq!(prng.generate_range(i64::MIN..=i64::MIN), i64::MIN);
+        assert_eq!(prng.generate_range(i8::MAX..=i8::MAX), i8::MAX);
+        assert!((i64::MIN..=0).contains(&prng.generate_range(i64::MIN..=0)));
+        let (mut negative, mut high) = (0, 0);
+        for _ in 0..1000 {
+            let number = prng.generate_range(i128::MIN..=i128::MAX);
+            if number < 0 {negative += 1;}
+            if number.unsigned_abs() >= 1 << 126 {high += 1;}
+        }
+        assert!((400..600).contains(&negative) && (400..600).contains(&high));
+    }
+    #[test]
+    #[should_panic(expected = "invalid integer range")]
+    fn invalid_signed_range() {
+        prng().generate_range(RangeInclusive::new(5_i8, -5));
+    }
+    #[test]
+    #[should_panic(expected = "invalid integer range")]
+    fn invalid_unsigned_range() {
+        prng().generate_range(RangeInclusive::new(5_u8, 4));
+    }
+    #[test]
+    fn signed_fixed_length() {
+        let mut prng = prng();
+        let (mut negative, mut positive) = (false, false);
+        for _ in 0..1000 {
+            let number = prng.generate_fixed_length::<i8>(3);
+            assert!((-128..=-100).contains(&number) || (100..=127).contains(&number));
+            negative |= number < 0;
+            positive |= number > 0;
+            assert!((-9..=9).contains(&prng.generate_fixed_length::<i16>(0)));
+            assert_eq!(prng.generate_fixed_length::<i32>(5).unsigned_abs().ilog10(), 4);
+            assert_eq!(prng.generate_fixed_length::<i128>(50).unsigned_abs().ilog10(), 38);
+        }
+        assert!(negative && positive);
+    }
+    #[test]
+    fn floats() {
+        let mut prng = prng();
+        for _ in 0..1000 {
+            assert!((0.0..1.0).contains(&prng.generate::<f64>()));
+            assert!((0.0..1.0).contains(&prng.generate::<f32>()));
+            assert!((-2.5..=7.5).contains(&prng.generate_range(-2.5_f64..=7.5)));
+            assert!((f32::MIN..=f32::MAX).contains(&prng.generate_range(f32::MIN..=f32::MAX)));
+        }
+        assert_eq!(prng.generate_range(1.5_f64..=1.5).to_bits(), 1.5_f64.to_bits());
+    }
+    #[test]
+    fn bools_and_chars() {
+        let mut prng = prng();
+        let mut trues = 0;
+        for _ in 0..1000 {
+            if prng.generate::<bool>() {trues += 1;}
+            assert!(prng.generate_range(true..=true));
+            let c = prng.generate_range('\u{D7FF}'..='\u{E000}');
+            assert!(c == '\u{D7FF}' || c == '\u{E000}');
+            assert!(prng.generate_range('a'..='z').is_ascii_lowercase());
+            prng.generate::<char>();
+        }
+        assert!((400..600).contains(&trues));
+    }
+    #[test]
+    #[should_panic(expected = "invalid floating point range")]
+    fn invalid_float_range() {
+        prng().generate_range(f64::NAN..=1.0);
+    }
+}